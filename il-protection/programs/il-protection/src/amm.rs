@@ -0,0 +1,255 @@
+use crate::{error::ErrorCode, BPS_DENOMINATOR};
+use anchor_lang::prelude::*;
+
+pub const AMM_PROGRAM_ID: Pubkey = pubkey!("6ayQJu8ZmfVfsincKddpZ34VGJHw98HSTBjNjDwrhrJ4");
+
+/// Read-only mirror of amm-program's `Config` account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AmmConfig {
+    pub seed: u64,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub fee: u16,
+    pub locked: bool,
+    pub lp_bump: u8,
+    pub bump: u8,
+}
+
+impl AmmConfig {
+    /// Anchor discriminator of amm-program's `Config`.
+    pub const DISCRIMINATOR: [u8; 8] = [155, 12, 170, 224, 30, 250, 204, 130];
+}
+
+impl AccountDeserialize for AmmConfig {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        require!(
+            buf.starts_with(&Self::DISCRIMINATOR),
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        let mut data = &buf[Self::DISCRIMINATOR.len()..];
+        AnchorDeserialize::deserialize(&mut data)
+            .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+    }
+}
+
+// Never written by this program.
+impl AccountSerialize for AmmConfig {}
+
+impl Owner for AmmConfig {
+    fn owner() -> Pubkey {
+        AMM_PROGRAM_ID
+    }
+}
+
+/// An AMM pool's reserves, with the side denominated in the pool mint
+/// marked as the stable side.
+///
+/// Reserves can be moved within a transaction, so they are only used for
+/// the spot price an `AmmOracle` follows and for the pool's invariant
+/// `x × y`, which swaps cannot shrink. Positions are valued at the oracle
+/// price.
+pub struct Reserves {
+    pub x: u64,
+    pub y: u64,
+    pub stable_is_x: bool,
+}
+
+impl Reserves {
+    /// Reserves of `config`'s pool, which must have a side in `pool_mint`.
+    pub fn new(config: &AmmConfig, x: u64, y: u64, pool_mint: Pubkey) -> Result<Self> {
+        let stable_is_x = config.mint_x == pool_mint;
+        require!(
+            stable_is_x || config.mint_y == pool_mint,
+            ErrorCode::UnsupportedAmmPool
+        );
+        Ok(Self { x, y, stable_is_x })
+    }
+
+    /// Stable tokens per other token at the pool's spot price, as Q64.64.
+    pub fn spot_price(&self) -> Result<u128> {
+        let (stable_reserve, other_reserve) = self.sides(self.x, self.y);
+        require!(
+            stable_reserve > 0 && other_reserve > 0,
+            ErrorCode::EmptyPool
+        );
+        Ok(((stable_reserve as u128) << 64) / other_reserve as u128)
+    }
+
+    /// Tokens of each side redeemable for `lp_amount` out of `lp_supply`
+    /// once arbitrage brings the pool to `price`.
+    pub fn position(&self, price: u128, lp_amount: u64, lp_supply: u64) -> Result<(u64, u64)> {
+        require!(lp_supply > 0, ErrorCode::EmptyPool);
+        let root_k = isqrt(self.x as u128 * self.y as u128);
+        let sqrt_price = isqrt(price); // Q32.32
+        require!(sqrt_price > 0, ErrorCode::EmptyPool);
+
+        let stable = root_k.checked_mul(sqrt_price).ok_or(ErrorCode::Overflow)? >> 32;
+        let other = (root_k << 32) / sqrt_price;
+        let stable = share_of(stable, lp_amount, lp_supply)?;
+        let other = share_of(other, lp_amount, lp_supply)?;
+        Ok(if self.stable_is_x {
+            (stable, other)
+        } else {
+            (other, stable)
+        })
+    }
+
+    /// Value of `x` and `y` in the stable side at `price`.
+    pub fn value(&self, price: u128, x: u64, y: u64) -> Result<u64> {
+        let (stable, other) = self.sides(x, y);
+        let other_value = (other as u128)
+            .checked_mul(price)
+            .ok_or(ErrorCode::Overflow)?
+            >> 64;
+        u64::try_from(other_value)
+            .ok()
+            .and_then(|v| v.checked_add(stable))
+            .ok_or(ErrorCode::Overflow.into())
+    }
+
+    /// `(stable, other)` from amounts of `x` and `y`.
+    fn sides(&self, x: u64, y: u64) -> (u64, u64) {
+        if self.stable_is_x {
+            (x, y)
+        } else {
+            (y, x)
+        }
+    }
+}
+
+/// Impermanent loss of a position against holding `hold_x` and `hold_y`,
+/// both valued at `price`: returns the loss in stable units and in basis
+/// points of the hold value.
+pub fn impermanent_loss(
+    reserves: &Reserves,
+    price: u128,
+    hold_x: u64,
+    hold_y: u64,
+    lp_amount: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64)> {
+    let hold_value = reserves.value(price, hold_x, hold_y)?;
+    let (x, y) = reserves.position(price, lp_amount, lp_supply)?;
+    let lp_value = reserves.value(price, x, y)?;
+
+    let loss = hold_value.saturating_sub(lp_value);
+    if hold_value == 0 {
+        return Ok((0, 0));
+    }
+    let loss_bps = mul_div(loss, BPS_DENOMINATOR, hold_value)?;
+    Ok((loss, loss_bps))
+}
+
+fn share_of(amount: u128, lp_amount: u64, lp_supply: u64) -> Result<u64> {
+    let value = amount
+        .checked_mul(lp_amount as u128)
+        .ok_or(ErrorCode::Overflow)?
+        / lp_supply as u128;
+    u64::try_from(value).map_err(|_| ErrorCode::Overflow.into())
+}
+
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+fn mul_div(a: u64, b: u64, c: u64) -> Result<u64> {
+    let value = (a as u128)
+        .checked_mul(b as u128)
+        .and_then(|v| v.checked_div(c as u128))
+        .ok_or(ErrorCode::Overflow)?;
+    u64::try_from(value).map_err(|_| ErrorCode::Overflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_a_position_at_spot_price() {
+        let reserves = Reserves {
+            x: 1_000,
+            y: 4_000,
+            stable_is_x: false,
+        };
+        let price = reserves.spot_price().unwrap();
+        assert_eq!(price, 4 << 64);
+        assert_eq!(reserves.position(price, 100, 1_000).unwrap(), (100, 400));
+        // 100 x at 4 stable each, plus 400 stable
+        assert_eq!(reserves.value(price, 100, 400).unwrap(), 800);
+    }
+
+    #[test]
+    fn measures_loss_against_holding() {
+        // 10% of a 1_000 / 1_000 pool, then the price of y quadruples
+        let reserves = Reserves {
+            x: 2_000,
+            y: 500,
+            stable_is_x: true,
+        };
+        let price = reserves.spot_price().unwrap();
+        let (loss, loss_bps) = impermanent_loss(&reserves, price, 100, 100, 100, 1_000).unwrap();
+        assert_eq!(loss, 100);
+        assert_eq!(loss_bps, 2_000);
+    }
+
+    #[test]
+    fn no_loss_when_price_is_unchanged() {
+        let reserves = Reserves {
+            x: 1_000,
+            y: 1_000,
+            stable_is_x: true,
+        };
+        let price = reserves.spot_price().unwrap();
+        assert_eq!(
+            impermanent_loss(&reserves, price, 100, 100, 100, 1_000).unwrap(),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn swaps_do_not_move_the_position_at_a_fixed_price() {
+        // A 1_000 / 1_000 pool after a swap pushed y to four times its price
+        let reserves = Reserves {
+            x: 2_000,
+            y: 500,
+            stable_is_x: true,
+        };
+        assert_eq!(reserves.position(1 << 64, 100, 1_000).unwrap(), (100, 100));
+        assert_eq!(
+            impermanent_loss(&reserves, 1 << 64, 100, 100, 100, 1_000).unwrap(),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn takes_integer_square_roots() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn checks_the_config_discriminator() {
+        let mut data = vec![0u8; 8 + 8 + 32 + 32 + 2 + 1 + 1 + 1];
+        assert!(AmmConfig::try_deserialize(&mut data.as_slice()).is_err());
+        data[..8].copy_from_slice(&AmmConfig::DISCRIMINATOR);
+        data[8] = 7;
+        let config = AmmConfig::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(config.seed, 7);
+    }
+}
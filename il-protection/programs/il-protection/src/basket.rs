@@ -0,0 +1,164 @@
+use crate::{error::ErrorCode, CollateralAsset, PoolConfig, BPS_DENOMINATOR};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Value of `amount` of a basket asset in pool-mint units, rounded down.
+pub fn haircut_value(amount: u64, haircut_bps: u16) -> Result<u64> {
+    mul_div(
+        amount,
+        BPS_DENOMINATOR - haircut_bps as u64,
+        BPS_DENOMINATOR,
+    )
+}
+
+/// Amount of a basket asset worth `value` after its haircut, rounded down.
+pub fn face_amount(value: u64, haircut_bps: u16) -> Result<u64> {
+    mul_div(value, BPS_DENOMINATOR, BPS_DENOMINATOR - haircut_bps as u64)
+}
+
+/// Pool assets backing shares: the pool vault plus every basket vault at its
+/// haircut value.
+///
+/// `basket` holds a `(CollateralAsset, vault)` pair for each registered asset,
+/// so a caller cannot leave a vault out to move the share price.
+pub fn pool_assets(
+    pool_config: &Account<PoolConfig>,
+    pool_vault_amount: u64,
+    basket: &[AccountInfo],
+) -> Result<u64> {
+    require!(
+        basket.len() == 2 * pool_config.collateral_assets as usize,
+        ErrorCode::BasketMismatch
+    );
+
+    let mut assets = pool_vault_amount;
+    let mut seen = Vec::with_capacity(pool_config.collateral_assets as usize);
+    for pair in basket.chunks(2) {
+        let asset = load_asset(&pair[0], &pool_config.key())?;
+        require!(!seen.contains(&asset.mint), ErrorCode::BasketMismatch);
+        require_keys_eq!(pair[1].key(), asset.vault, ErrorCode::BasketMismatch);
+        seen.push(asset.mint);
+
+        let vault = TokenAccount::try_deserialize(&mut &pair[1].try_borrow_data()?[..])?;
+        assets = assets
+            .checked_add(haircut_value(vault.amount, asset.haircut_bps)?)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+    Ok(assets)
+}
+
+/// Assets new shares are priced against: `pool_assets` plus premium already
+/// earned but not yet released from the premium vault, so stakers cannot buy
+/// into it at the old share price.
+pub fn staking_assets(
+    pool_config: &Account<PoolConfig>,
+    pool_vault_amount: u64,
+    premium_vault_amount: u64,
+    basket: &[AccountInfo],
+) -> Result<u64> {
+    let earned = pool_config
+        .earned_premium(Clock::get()?.unix_timestamp)?
+        .min(premium_vault_amount);
+    pool_assets(pool_config, pool_vault_amount, basket)?
+        .checked_add(earned)
+        .ok_or(ErrorCode::Overflow.into())
+}
+
+/// A payout in a basket asset, read from `[collateral_asset, vault, mint,
+/// destination]` in the remaining accounts.
+pub struct Settlement<'info> {
+    pub asset: CollateralAsset,
+    vault: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    decimals: u8,
+}
+
+impl<'info> Settlement<'info> {
+    pub fn load(
+        pool_config: &Pubkey,
+        accounts: &[AccountInfo<'info>],
+        recipient: &Pubkey,
+    ) -> Result<Self> {
+        let [asset, vault, mint, destination] = accounts else {
+            return err!(ErrorCode::UnsupportedCollateral);
+        };
+        let collateral = load_asset(asset, pool_config)?;
+        require_keys_eq!(vault.key(), collateral.vault, ErrorCode::BasketMismatch);
+        require_keys_eq!(
+            mint.key(),
+            collateral.mint,
+            ErrorCode::UnsupportedCollateral
+        );
+
+        let decimals = Mint::try_deserialize(&mut &mint.try_borrow_data()?[..])?.decimals;
+        let to = TokenAccount::try_deserialize(&mut &destination.try_borrow_data()?[..])?;
+        require_keys_eq!(to.mint, collateral.mint, ErrorCode::UnsupportedCollateral);
+        require_keys_eq!(to.owner, *recipient, ErrorCode::Unauthorized);
+
+        Ok(Self {
+            asset: collateral,
+            vault: vault.clone(),
+            mint: mint.clone(),
+            destination: destination.clone(),
+            decimals,
+        })
+    }
+
+    pub fn pay(
+        &self,
+        pool_config: &Account<'info, PoolConfig>,
+        token_program: &Interface<'info, TokenInterface>,
+        amount: u64,
+    ) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"pool_config",
+            &pool_config.pool_id.to_le_bytes(),
+            &[pool_config.bump],
+        ]];
+        let cpi_accounts = TransferChecked {
+            from: self.vault.clone(),
+            to: self.destination.clone(),
+            mint: self.mint.clone(),
+            authority: pool_config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts)
+            .with_signer(signer_seeds);
+        transfer_checked(cpi_ctx, amount, self.decimals)
+    }
+}
+
+fn load_asset(info: &AccountInfo, pool_config: &Pubkey) -> Result<CollateralAsset> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::BasketMismatch);
+    let asset = CollateralAsset::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    require_keys_eq!(asset.pool_config, *pool_config, ErrorCode::BasketMismatch);
+    Ok(asset)
+}
+
+fn mul_div(a: u64, b: u64, c: u64) -> Result<u64> {
+    let value = (a as u128)
+        .checked_mul(b as u128)
+        .and_then(|v| v.checked_div(c as u128))
+        .ok_or(ErrorCode::Overflow)?;
+    u64::try_from(value).map_err(|_| ErrorCode::Overflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn haircut_discounts_the_vault() {
+        assert_eq!(haircut_value(1_000, 0).unwrap(), 1_000);
+        assert_eq!(haircut_value(1_000, 250).unwrap(), 975);
+    }
+
+    #[test]
+    fn face_amount_inverts_the_haircut() {
+        assert_eq!(face_amount(975, 250).unwrap(), 1_000);
+        // rounded down, in the pool's favour
+        assert_eq!(face_amount(10, 300).unwrap(), 10);
+    }
+}
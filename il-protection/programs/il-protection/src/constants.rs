@@ -2,3 +2,27 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+pub const BPS_DENOMINATOR: u64 = 10_000; // 100% in basis points
+
+pub const SHARE_PRICE_SCALE: u64 = 1_000_000_000; // fixed-point scale for per-share values
+
+pub const PREMIUM_ACCRUAL_SCALE: u128 = 1_000_000_000_000; // fixed-point scale for premium accrual
+
+pub const PREMIUM_PERIOD: i64 = 2_592_000; // premium_rate is quoted per 30 days
+
+pub const UTILIZATION_KINK_BPS: u64 = 8_000; // utilization where the curve steepens
+
+pub const UTILIZATION_SLOPE_BPS: u64 = 5_000; // extra premium at the kink (+50%)
+
+pub const UTILIZATION_JUMP_BPS: u64 = 30_000; // extra premium from kink to full (+300%)
+
+pub const REJECT_QUORUM_BPS: u64 = 5_000; // share of total_shares whose votes reject a claim
+
+pub const MAX_CLAIMS_PER_POLICY: u32 = 3; // reviewed claims a policy may submit; rejection after the last is final
+
+pub const ORACLE_MOVE_BPS_PER_SECOND: u64 = 1; // most an AMM oracle follows spot per elapsed second
+
+pub const ORACLE_MAX_MOVE_BPS: u64 = 100; // most an AMM oracle follows spot in one update
+
+pub const WITHDRAWAL_WINDOW: i64 = 172_800; // seconds after the cooldown a withdrawal can be completed
@@ -16,4 +16,112 @@ pub enum ErrorCode {
 
     #[msg("Not enough shares to withdraw")]
     NotEnoughShares,
+
+    #[msg("Invalid policy duration")]
+    InvalidDuration,
+
+    #[msg("Invalid withdrawal cooldown")]
+    InvalidCooldown,
+
+    #[msg("No withdrawal has been requested")]
+    NoPendingWithdrawal,
+
+    #[msg("Withdrawal cooldown has not elapsed")]
+    CooldownNotElapsed,
+
+    #[msg("Withdrawal request has lapsed and must be requested again")]
+    WithdrawalExpired,
+
+    #[msg("Not authorized")]
+    Unauthorized,
+
+    #[msg("Pool is paused")]
+    PoolPaused,
+
+    #[msg("Pool is not paused")]
+    PoolNotPaused,
+
+    #[msg("Basis points must be at most 10_000")]
+    InvalidBasisPoints,
+
+    #[msg("Claim exceeds the auto-settle limit and must be submitted for review")]
+    ClaimRequiresReview,
+
+    #[msg("Policy already has a claim under review")]
+    ClaimPending,
+
+    #[msg("Claim is not pending")]
+    ClaimNotPending,
+
+    #[msg("Policy has used all of its claim submissions")]
+    ClaimLimitReached,
+
+    #[msg("Claim challenge window is still open")]
+    ChallengeWindowOpen,
+
+    #[msg("Claim challenge window has closed")]
+    ChallengeWindowClosed,
+
+    #[msg("Invalid challenge window")]
+    InvalidChallengeWindow,
+
+    #[msg("Pool has no shares outstanding")]
+    EmptyPool,
+
+    #[msg("Pool has no assets backing its shares")]
+    PoolInsolvent,
+
+    #[msg("Not enough unlocked collateral to back the coverage")]
+    InsufficientCapacity,
+
+    #[msg("Policy has expired")]
+    PolicyExpired,
+
+    #[msg("Policy has not expired yet")]
+    PolicyNotExpired,
+
+    #[msg("A pool cannot reinsure itself")]
+    SamePool,
+
+    #[msg("Pools must share a mint")]
+    MintMismatch,
+
+    #[msg("Reinsurance treaty has expired")]
+    TreatyExpired,
+
+    #[msg("Reinsurance treaty has not expired yet")]
+    TreatyNotExpired,
+
+    #[msg("Policy is already held by this owner")]
+    SameOwner,
+
+    #[msg("AMM pool has no side in the pool mint")]
+    UnsupportedAmmPool,
+
+    #[msg("Policy covers an AMM position")]
+    LpPolicy,
+
+    #[msg("Policy does not cover an AMM position")]
+    NotLpPolicy,
+
+    #[msg("Insured LP tokens are no longer held")]
+    LpPositionMissing,
+
+    #[msg("AMM spot price has moved away from the oracle price")]
+    OracleNotSettled,
+
+    #[msg("Impermanent loss is below the policy threshold")]
+    LossBelowThreshold,
+
+    #[msg("Haircut must be below 100%")]
+    InvalidHaircut,
+
+    #[msg("Collateral must use the pool mint's decimals")]
+    DecimalsMismatch,
+
+    #[msg("Every basket asset and its vault must be passed once")]
+    BasketMismatch,
+
+    #[msg("Mint is not accepted as collateral by this pool")]
+    UnsupportedCollateral,
 }
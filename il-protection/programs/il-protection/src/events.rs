@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PoolInitialized {
+    pub pool_config: Pubkey,
+    pub pool_id: u64,
+    pub admin: Pubkey,
+    pub pool_mint: Pubkey,
+    pub premium_rate: u16,
+    pub threshold_max: u16,
+    pub withdrawal_cooldown: i64,
+    pub cancellation_fee_bps: u16,
+}
+
+#[event]
+pub struct CollateralStaked {
+    pub pool_config: Pubkey,
+    pub underwriter: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub total_shares: u64, // pool total after the stake
+}
+
+#[event]
+pub struct CollateralAssetConfigured {
+    pub pool_config: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub haircut_bps: u16,
+}
+
+#[event]
+pub struct AmmPoolAdded {
+    pub pool_config: Pubkey,
+    pub amm_config: Pubkey,
+    pub price: u128, // oracle's starting price, Q64.64
+}
+
+#[event]
+pub struct WithdrawalRequested {
+    pub pool_config: Pubkey,
+    pub underwriter: Pubkey,
+    pub shares: u64,
+    pub requested_at: i64,
+}
+
+#[event]
+pub struct WithdrawalCancelled {
+    pub pool_config: Pubkey,
+    pub underwriter: Pubkey,
+    pub shares: u64, // returned from escrow
+}
+
+#[event]
+pub struct WithdrawalCompleted {
+    pub pool_config: Pubkey,
+    pub underwriter: Pubkey,
+    pub shares: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub realized_pnl: i64, // gain or loss on the burned shares against their entry price
+    pub untracked_shares: u64, // burned shares with no entry price, left out of realized_pnl
+    pub total_shares: u64, // pool total after the burn
+}
+
+#[event]
+pub struct PolicyPurchased {
+    pub pool_config: Pubkey,
+    pub policy: Pubkey,
+    pub lp_owner: Pubkey,
+    pub threshold: u16,
+    pub coverage_amount: u64,
+    pub premium: u64,
+    pub locked_shares: u64,
+    pub start_time: i64,
+    pub expiry_time: i64,
+}
+
+#[event]
+pub struct PolicyTransferred {
+    pub pool_config: Pubkey,
+    pub from_policy: Pubkey,
+    pub to_policy: Pubkey,
+    pub from_owner: Pubkey,
+    pub to_owner: Pubkey,
+}
+
+#[event]
+pub struct PolicyCancelled {
+    pub pool_config: Pubkey,
+    pub policy: Pubkey,
+    pub lp_owner: Pubkey,
+    pub refund: u64,        // unused premium returned to the LP
+    pub fee: u64,           // unused premium kept by underwriters
+    pub locked_shares: u64, // shares released by the policy
+}
+
+#[event]
+pub struct ClaimPaid {
+    pub pool_config: Pubkey,
+    pub policy: Pubkey,
+    pub lp_owner: Pubkey,
+    pub threshold: u16,     // IL reported by the claimant
+    pub mint: Pubkey,       // the policy's settlement mint
+    pub amount: u64,        // payout taken from that mint's vault
+    pub locked_shares: u64, // shares released by the policy
+}
+
+/// Loss socialized across share holders by a claim payout.
+#[event]
+pub struct ClaimLoss {
+    pub pool_config: Pubkey,
+    pub policy: Pubkey,
+    pub amount: u64,
+    pub total_shares: u64,   // shares bearing the loss
+    pub loss_per_share: u64, // amount / total_shares, scaled by SHARE_PRICE_SCALE
+}
+
+#[event]
+pub struct PolicyExpired {
+    pub pool_config: Pubkey,
+    pub policy: Pubkey,
+    pub lp_owner: Pubkey,
+    pub premium: u64,       // total premium earned by underwriters
+    pub locked_shares: u64, // shares released by the policy
+}
+
+#[event]
+pub struct ReinsurancePurchased {
+    pub treaty: Pubkey,
+    pub primary_pool: Pubkey,
+    pub reinsurer_pool: Pubkey,
+    pub attachment: u64,
+    pub limit: u64,
+    pub premium: u64,
+    pub locked_shares: u64, // reinsurer shares backing the limit
+    pub expiry_time: i64,
+}
+
+#[event]
+pub struct ReinsuranceRecovered {
+    pub treaty: Pubkey,
+    pub primary_pool: Pubkey,
+    pub reinsurer_pool: Pubkey,
+    pub amount: u64,
+    pub recovered: u64, // treaty total after this recovery
+}
+
+#[event]
+pub struct ReinsuranceExpired {
+    pub treaty: Pubkey,
+    pub primary_pool: Pubkey,
+    pub reinsurer_pool: Pubkey,
+    pub recovered: u64,
+    pub locked_shares: u64, // reinsurer shares released
+}
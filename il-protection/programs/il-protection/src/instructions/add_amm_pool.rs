@@ -0,0 +1,84 @@
+use crate::{
+    amm::{AmmConfig, Reserves, AMM_PROGRAM_ID},
+    error::ErrorCode,
+    events::AmmPoolAdded,
+    AmmOracle, PoolConfig,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct AddAmmPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::Unauthorized,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        seeds = [b"config", amm_config.seed.to_le_bytes().as_ref()],
+        seeds::program = AMM_PROGRAM_ID,
+        bump = amm_config.bump
+    )]
+    pub amm_config: Account<'info, AmmConfig>,
+
+    #[account(
+        associated_token::mint = amm_config.mint_x,
+        associated_token::authority = amm_config,
+        associated_token::token_program = token_program
+    )]
+    pub amm_vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        associated_token::mint = amm_config.mint_y,
+        associated_token::authority = amm_config,
+        associated_token::token_program = token_program
+    )]
+    pub amm_vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = AmmOracle::DISCRIMINATOR.len() + AmmOracle::INIT_SPACE,
+        seeds = [b"amm_oracle", pool_config.key().as_ref(), amm_config.key().as_ref()],
+        bump
+    )]
+    pub amm_oracle: Account<'info, AmmOracle>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddAmmPool<'info> {
+    /// Lets the pool insure positions in `amm_config`'s pool. The oracle
+    /// starts at the current spot price, so the admin vouches for it.
+    pub fn add_amm_pool(&mut self, bumps: &AddAmmPoolBumps) -> Result<()> {
+        let reserves = Reserves::new(
+            &self.amm_config,
+            self.amm_vault_x.amount,
+            self.amm_vault_y.amount,
+            self.pool_config.pool_mint,
+        )?;
+        let price = reserves.spot_price()?;
+
+        self.amm_oracle.set_inner(AmmOracle {
+            pool_config: self.pool_config.key(),
+            amm_config: self.amm_config.key(),
+            price,
+            updated_at: Clock::get()?.unix_timestamp,
+            bump: bumps.amm_oracle,
+        });
+
+        emit!(AmmPoolAdded {
+            pool_config: self.pool_config.key(),
+            amm_config: self.amm_config.key(),
+            price,
+        });
+        Ok(())
+    }
+}
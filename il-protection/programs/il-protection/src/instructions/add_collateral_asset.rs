@@ -0,0 +1,97 @@
+use crate::{
+    error::ErrorCode, events::CollateralAssetConfigured, CollateralAsset, PoolConfig,
+    BPS_DENOMINATOR,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct AddCollateralAsset<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::Unauthorized,
+        has_one = pool_mint,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = CollateralAsset::DISCRIMINATOR.len() + CollateralAsset::INIT_SPACE,
+        seeds = [b"collateral", pool_config.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub collateral_asset: Account<'info, CollateralAsset>,
+
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = pool_config,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = mint.key() != pool_mint.key() @ ErrorCode::MintMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mint::token_program = token_program)]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddCollateralAsset<'info> {
+    pub fn add_collateral_asset(
+        &mut self,
+        haircut_bps: u16,
+        bumps: &AddCollateralAssetBumps,
+    ) -> Result<()> {
+        require!(
+            (haircut_bps as u64) < BPS_DENOMINATOR,
+            ErrorCode::InvalidHaircut
+        );
+        // Shares and coverage are counted in pool-mint units, one for one.
+        require!(
+            self.mint.decimals == self.pool_mint.decimals,
+            ErrorCode::DecimalsMismatch
+        );
+
+        self.pool_config.collateral_assets = self
+            .pool_config
+            .collateral_assets
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+
+        self.collateral_asset.set_inner(CollateralAsset {
+            pool_config: self.pool_config.key(),
+            mint: self.mint.key(),
+            vault: self.vault.key(),
+            haircut_bps,
+            bump: bumps.collateral_asset,
+        });
+
+        emit!(CollateralAssetConfigured {
+            pool_config: self.pool_config.key(),
+            mint: self.mint.key(),
+            vault: self.vault.key(),
+            haircut_bps,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use super::buy_protection::*;
+use crate::{
+    amm::{AmmConfig, Reserves, AMM_PROGRAM_ID},
+    error::ErrorCode,
+    transfer_tokens, AmmOracle,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct BuyLpProtection<'info> {
+    pub protection: BuyProtection<'info>,
+
+    #[account(
+        seeds = [b"config", amm_config.seed.to_le_bytes().as_ref()],
+        seeds::program = AMM_PROGRAM_ID,
+        bump = amm_config.bump
+    )]
+    pub amm_config: Account<'info, AmmConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_oracle", protection.pool_config.key().as_ref(), amm_config.key().as_ref()],
+        bump = amm_oracle.bump
+    )]
+    pub amm_oracle: Account<'info, AmmOracle>,
+
+    #[account(
+        associated_token::mint = amm_config.mint_x,
+        associated_token::authority = amm_config,
+        associated_token::token_program = protection.token_program
+    )]
+    pub amm_vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        associated_token::mint = amm_config.mint_y,
+        associated_token::authority = amm_config,
+        associated_token::token_program = protection.token_program
+    )]
+    pub amm_vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"lp", amm_config.key().as_ref()],
+        seeds::program = AMM_PROGRAM_ID,
+        bump = amm_config.lp_bump
+    )]
+    pub mint_lp: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = protection.lp_owner,
+        associated_token::token_program = protection.token_program
+    )]
+    pub lp_owner_lp_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // Holds the insured LP tokens until the policy is claimed, cancelled or
+    // expired.
+    #[account(
+        init,
+        payer = protection.payer,
+        associated_token::mint = mint_lp,
+        associated_token::authority = protection.policy,
+        associated_token::token_program = token_program
+    )]
+    pub lp_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuyLpProtection<'info> {
+    pub fn buy_lp_protection(
+        &mut self,
+        threshold: u16,
+        lp_amount: u64,
+        duration: i64,
+        basket: &[AccountInfo<'info>],
+        bumps: &BuyLpProtectionBumps,
+    ) -> Result<()> {
+        require!(lp_amount > 0, ErrorCode::InvalidAmount);
+        require!(
+            self.lp_owner_lp_ata.amount >= lp_amount,
+            ErrorCode::LpPositionMissing
+        );
+
+        // Coverage is the position's value at the oracle price; claims pay
+        // the measured loss against holding the same tokens, up to that
+        // amount.
+        let reserves = Reserves::new(
+            &self.amm_config,
+            self.amm_vault_x.amount,
+            self.amm_vault_y.amount,
+            self.protection.pool_mint.key(),
+        )?;
+        let spot = reserves.spot_price()?;
+        self.amm_oracle.update(spot, Clock::get()?.unix_timestamp)?;
+        // Spot far from the oracle means the position would be priced on a
+        // stale value, e.g. just after the reserves were pushed to buy cover
+        // cheaply before a move the oracle is still following.
+        require!(
+            self.amm_oracle.is_settled(spot)?,
+            ErrorCode::OracleNotSettled
+        );
+        let price = self.amm_oracle.price;
+        let (hold_x, hold_y) = reserves.position(price, lp_amount, self.mint_lp.supply)?;
+        let coverage_amount = reserves.value(price, hold_x, hold_y)?;
+
+        self.protection.buy_protection(
+            threshold,
+            coverage_amount,
+            duration,
+            basket,
+            &bumps.protection,
+        )?;
+
+        let policy = &mut self.protection.policy;
+        policy.amm_config = self.amm_config.key();
+        policy.lp_amount = lp_amount;
+        policy.hold_x = hold_x;
+        policy.hold_y = hold_y;
+
+        transfer_tokens(
+            &self.lp_owner_lp_ata,
+            &self.lp_escrow,
+            &self.mint_lp,
+            &self.protection.lp_owner,
+            &self.token_program,
+            lp_amount,
+            None,
+        )
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{error::ErrorCode, transfer_tokens, Policy, PoolConfig};
+use crate::{
+    basket, error::ErrorCode, events::PolicyPurchased, pricing::quote_premium, share_math,
+    transfer_tokens, Policy, PoolConfig,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
@@ -13,6 +16,7 @@ pub struct BuyProtection<'info> {
         mut,
         has_one = pool_vault,
         has_one = pool_mint,
+        has_one = premium_vault,
         seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
         bump = pool_config.bump
     )]
@@ -35,6 +39,9 @@ pub struct BuyProtection<'info> {
     )]
     pub pool_vault: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(mut, token::token_program = token_program)]
+    pub premium_vault: InterfaceAccount<'info, TokenAccount>,
+
     #[account(mint::token_program = token_program)]
     pub pool_mint: InterfaceAccount<'info, Mint>,
 
@@ -57,48 +64,60 @@ impl<'info> BuyProtection<'info> {
         threshold: u16,
         coverage_amount: u64,
         duration: i64,
+        basket: &[AccountInfo<'info>],
         bumps: &BuyProtectionBumps,
     ) -> Result<()> {
-        require!(
-            threshold <= self.pool_config.threshold_max,
-            ErrorCode::InvalidThreshold
-        );
-        require!(coverage_amount > 0, ErrorCode::InvalidAmount);
-
-        let duration_order = 2_592_000u128;
-        let premium_order = 10_000u128;
-
-        let total_amount = (coverage_amount as u128)
-            .checked_mul(self.pool_config.premium_rate as u128)
-            .unwrap()
-            .checked_div(premium_order)
-            .unwrap()
-            .checked_mul(duration as u128)
-            .unwrap()
-            .checked_div(duration_order)
-            .unwrap() as u64;
-
-        self.transfer_amount(total_amount)?;
+        require!(!self.pool_config.paused, ErrorCode::PoolPaused);
 
-        let locked_shares = (total_amount as u128)
-            .checked_mul(self.pool_config.total_shares as u128)
-            .unwrap()
-            .checked_div(self.pool_vault.amount as u128)
-            .unwrap() as u64;
-
-        self.pool_config.locked_shares = self
+        // The premium is held in reserve and vests to underwriters over the
+        // policy, so collateral is locked against the coverage itself.
+        let pool_assets = basket::pool_assets(&self.pool_config, self.pool_vault.amount, basket)?;
+        let locked_shares = share_math::shares_for_assets(
+            coverage_amount,
+            self.pool_config.total_shares,
+            pool_assets,
+        )?;
+        let total_amount = quote_premium(
+            &self.pool_config,
+            threshold,
+            coverage_amount,
+            duration,
+            locked_shares,
+        )?;
+        let pool_locked_shares = self
             .pool_config
             .locked_shares
             .checked_add(locked_shares)
             .ok_or(ErrorCode::Overflow)?;
+        require!(
+            pool_locked_shares <= self.pool_config.total_shares,
+            ErrorCode::InsufficientCapacity
+        );
+
+        self.transfer_amount(total_amount)?;
+
+        self.pool_config.locked_shares = pool_locked_shares;
 
         self.initialize_policy(
             threshold,
             coverage_amount,
+            total_amount,
             locked_shares,
             duration,
             bumps.policy,
         )?;
+
+        emit!(PolicyPurchased {
+            pool_config: self.pool_config.key(),
+            policy: self.policy.key(),
+            lp_owner: self.lp_owner.key(),
+            threshold,
+            coverage_amount,
+            premium: total_amount,
+            locked_shares,
+            start_time: self.policy.start_time,
+            expiry_time: self.policy.expiry_time,
+        });
         Ok(())
     }
 
@@ -106,6 +125,7 @@ impl<'info> BuyProtection<'info> {
         &mut self,
         threshold: u16,
         coverage_amount: u64,
+        premium: u64,
         locked_shares: u64,
         duration: i64,
         bump: u8,
@@ -116,21 +136,31 @@ impl<'info> BuyProtection<'info> {
             .ok_or(ErrorCode::Overflow)?;
         self.policy.set_inner(Policy {
             pool_config: self.pool_config.key(),
+            lp_owner: self.lp_owner.key(),
             policy_id: self.pool_config.pool_id,
             coverage_amount,
+            premium,
+            premium_vested: 0,
             threshold,
             locked_shares,
             start_time,
             expiry_time,
+            claim_pending: false,
+            claims_submitted: 0,
+            settlement_mint: self.pool_mint.key(),
+            amm_config: Pubkey::default(),
+            lp_amount: 0,
+            hold_x: 0,
+            hold_y: 0,
             bump,
         });
-        Ok(())
+        self.pool_config.add_cover(&*self.policy, start_time)
     }
 
     pub fn transfer_amount(&mut self, amount: u64) -> Result<()> {
         transfer_tokens(
             &self.lp_owner_ata,
-            &self.pool_vault,
+            &self.premium_vault,
             &self.pool_mint,
             &self.lp_owner,
             &self.token_program,
@@ -0,0 +1,156 @@
+use crate::{
+    basket, error::ErrorCode, events::ReinsurancePurchased, pricing::quote_premium, share_math,
+    transfer_tokens, PoolConfig, ReinsuranceTreaty,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct BuyReinsurance<'info> {
+    pub admin: Signer<'info>,
+
+    // The reinsurer's capital is locked by the treaty, so its admin must agree.
+    pub reinsurer_admin: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::Unauthorized,
+        has_one = pool_mint,
+        seeds = [b"pool_config", primary_pool.pool_id.to_le_bytes().as_ref()],
+        bump = primary_pool.bump
+    )]
+    pub primary_pool: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        constraint = reinsurer_pool.key() != primary_pool.key() @ ErrorCode::SamePool,
+        constraint = reinsurer_pool.admin == reinsurer_admin.key() @ ErrorCode::Unauthorized,
+        has_one = pool_mint @ ErrorCode::MintMismatch,
+        seeds = [b"pool_config", reinsurer_pool.pool_id.to_le_bytes().as_ref()],
+        bump = reinsurer_pool.bump
+    )]
+    pub reinsurer_pool: Account<'info, PoolConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ReinsuranceTreaty::DISCRIMINATOR.len() + ReinsuranceTreaty::INIT_SPACE,
+        seeds = [b"treaty", primary_pool.key().as_ref(), reinsurer_pool.key().as_ref()],
+        bump
+    )]
+    pub treaty: Account<'info, ReinsuranceTreaty>,
+
+    #[account(mut, address = primary_pool.pool_vault)]
+    pub primary_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = reinsurer_pool.pool_vault)]
+    pub reinsurer_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = reinsurer_pool.premium_vault)]
+    pub reinsurer_premium_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuyReinsurance<'info> {
+    pub fn buy_reinsurance(
+        &mut self,
+        attachment: u64,
+        limit: u64,
+        duration: i64,
+        reinsurer_basket: &[AccountInfo<'info>],
+        bumps: &BuyReinsuranceBumps,
+    ) -> Result<()> {
+        require!(!self.reinsurer_pool.paused, ErrorCode::PoolPaused);
+
+        let reinsurer_assets = basket::pool_assets(
+            &self.reinsurer_pool,
+            self.reinsurer_vault.amount,
+            reinsurer_basket,
+        )?;
+        let locked_shares = share_math::shares_for_assets(
+            limit,
+            self.reinsurer_pool.total_shares,
+            reinsurer_assets,
+        )?;
+        let pool_locked_shares = self
+            .reinsurer_pool
+            .locked_shares
+            .checked_add(locked_shares)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            pool_locked_shares <= self.reinsurer_pool.total_shares,
+            ErrorCode::InsufficientCapacity
+        );
+
+        // The layer is priced like a policy at the reinsurer's base threshold.
+        let premium = quote_premium(
+            &self.reinsurer_pool,
+            self.reinsurer_pool.threshold_max,
+            limit,
+            duration,
+            locked_shares,
+        )?;
+        self.reinsurer_pool.locked_shares = pool_locked_shares;
+
+        self.transfer_premium(premium)?;
+
+        let start_time = Clock::get()?.unix_timestamp;
+        let expiry_time = start_time
+            .checked_add(duration)
+            .ok_or(ErrorCode::Overflow)?;
+        self.treaty.set_inner(ReinsuranceTreaty {
+            primary_pool: self.primary_pool.key(),
+            reinsurer_pool: self.reinsurer_pool.key(),
+            attachment,
+            limit,
+            claims_paid_at_start: self.primary_pool.claims_paid,
+            recovered_at_start: self.primary_pool.reinsurance_recovered,
+            recovered: 0,
+            locked_shares,
+            premium,
+            premium_vested: 0,
+            start_time,
+            expiry_time,
+            bump: bumps.treaty,
+        });
+        self.reinsurer_pool.add_cover(&*self.treaty, start_time)?;
+
+        emit!(ReinsurancePurchased {
+            treaty: self.treaty.key(),
+            primary_pool: self.primary_pool.key(),
+            reinsurer_pool: self.reinsurer_pool.key(),
+            attachment,
+            limit,
+            premium,
+            locked_shares,
+            expiry_time,
+        });
+        Ok(())
+    }
+
+    pub fn transfer_premium(&mut self, amount: u64) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"pool_config",
+            &self.primary_pool.pool_id.to_le_bytes(),
+            &[self.primary_pool.bump],
+        ]];
+        transfer_tokens(
+            &self.primary_vault,
+            &self.reinsurer_premium_vault,
+            &self.pool_mint,
+            &self.primary_pool.to_account_info(),
+            &self.token_program,
+            amount,
+            Some(signer_seeds),
+        )
+    }
+}
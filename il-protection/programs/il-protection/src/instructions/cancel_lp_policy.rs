@@ -0,0 +1,51 @@
+use super::cancel_policy::*;
+use crate::{amm::AMM_PROGRAM_ID, error::ErrorCode, release_lp_escrow};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CancelLpPolicy<'info> {
+    pub protection: CancelPolicy<'info>,
+
+    #[account(
+        seeds = [b"lp", protection.policy.amm_config.as_ref()],
+        seeds::program = AMM_PROGRAM_ID,
+        bump
+    )]
+    pub mint_lp: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = protection.policy,
+        associated_token::token_program = protection.token_program
+    )]
+    pub lp_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = protection.lp_owner,
+        associated_token::token_program = protection.token_program
+    )]
+    pub lp_owner_lp_ata: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> CancelLpPolicy<'info> {
+    pub fn cancel_lp_policy(&mut self) -> Result<()> {
+        require!(
+            self.protection.policy.amm_config != Pubkey::default(),
+            ErrorCode::NotLpPolicy
+        );
+        self.protection.cancel()?;
+
+        release_lp_escrow(
+            &self.protection.policy,
+            &self.lp_escrow,
+            &self.lp_owner_lp_ata,
+            &self.mint_lp,
+            &self.protection.lp_owner,
+            &self.protection.token_program,
+        )
+    }
+}
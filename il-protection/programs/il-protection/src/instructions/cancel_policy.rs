@@ -0,0 +1,136 @@
+use crate::{
+    error::ErrorCode, events::PolicyCancelled, transfer_tokens, vest_premium, Policy, PoolConfig,
+    BPS_DENOMINATOR,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct CancelPolicy<'info> {
+    #[account(mut)]
+    pub lp_owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = pool_vault,
+        has_one = pool_mint,
+        has_one = premium_vault,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        close = lp_owner,
+        has_one = pool_config,
+        seeds = [b"policy", pool_config.key().as_ref(), lp_owner.key().as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = pool_config,
+        associated_token::token_program = token_program
+    )]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::token_program = token_program)]
+    pub premium_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = lp_owner,
+        associated_token::token_program = token_program
+    )]
+    pub lp_owner_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelPolicy<'info> {
+    pub fn cancel_policy(&mut self) -> Result<()> {
+        // AMM position policies also return their escrow via cancel_lp_policy.
+        require!(
+            self.policy.amm_config == Pubkey::default(),
+            ErrorCode::LpPolicy
+        );
+        self.cancel()
+    }
+
+    pub fn cancel(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(!self.policy.claim_pending, ErrorCode::ClaimPending);
+        require!(now < self.policy.expiry_time, ErrorCode::PolicyExpired);
+
+        // Underwriters keep what has been earned so far.
+        vest_premium(
+            &mut self.pool_config,
+            &mut *self.policy,
+            &self.premium_vault,
+            &self.pool_vault,
+            &self.pool_mint,
+            &self.token_program,
+            now,
+        )?;
+
+        let unused = self
+            .policy
+            .premium
+            .saturating_sub(self.policy.premium_vested);
+        let fee = (unused as u128 * self.pool_config.cancellation_fee_bps as u128
+            / BPS_DENOMINATOR as u128) as u64;
+        let refund = unused - fee;
+
+        self.pool_config.locked_shares = self
+            .pool_config
+            .locked_shares
+            .saturating_sub(self.policy.locked_shares);
+        self.pool_config.remove_cover(&*self.policy, now)?;
+        self.policy.premium_vested = self.policy.premium;
+
+        if fee > 0 {
+            self.transfer_from_reserve(&self.pool_vault, fee)?;
+        }
+        if refund > 0 {
+            self.transfer_from_reserve(&self.lp_owner_ata, refund)?;
+        }
+
+        emit!(PolicyCancelled {
+            pool_config: self.pool_config.key(),
+            policy: self.policy.key(),
+            lp_owner: self.lp_owner.key(),
+            refund,
+            fee,
+            locked_shares: self.policy.locked_shares,
+        });
+        Ok(())
+    }
+
+    pub fn transfer_from_reserve(
+        &self,
+        to: &InterfaceAccount<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"pool_config",
+            &self.pool_config.pool_id.to_le_bytes(),
+            &[self.pool_config.bump],
+        ]];
+        transfer_tokens(
+            &self.premium_vault,
+            to,
+            &self.pool_mint,
+            &self.pool_config.to_account_info(),
+            &self.token_program,
+            amount,
+            Some(signer_seeds),
+        )
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    close_token_account, error::ErrorCode, events::WithdrawalCancelled, transfer_tokens,
+    PoolConfig, UnderwriterStake,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(mut)]
+    pub underwriter: Signer<'info>,
+
+    #[account(
+        has_one = share_mint,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = pool_config,
+        has_one = underwriter,
+        seeds = [b"underwriter", pool_config.pool_id.to_le_bytes().as_ref(), underwriter.key().as_ref()],
+        bump = underwriter_stake.bump
+    )]
+    pub underwriter_stake: Account<'info, UnderwriterStake>,
+
+    #[account(mint::token_program = token_program)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = underwriter,
+        associated_token::token_program = token_program
+    )]
+    pub underwriter_share_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = underwriter_stake,
+        associated_token::token_program = token_program
+    )]
+    pub withdrawal_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelWithdrawal<'info> {
+    /// Returns queued shares to the holder, whether the request is still
+    /// cooling down or has lapsed.
+    pub fn cancel_withdrawal(&mut self) -> Result<()> {
+        let shares = self.withdrawal_escrow.amount;
+        require!(
+            self.underwriter_stake.pending_shares > 0,
+            ErrorCode::NoPendingWithdrawal
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"underwriter",
+            &self.pool_config.pool_id.to_le_bytes(),
+            self.underwriter.key.as_ref(),
+            &[self.underwriter_stake.bump],
+        ]];
+        transfer_tokens(
+            &self.withdrawal_escrow,
+            &self.underwriter_share_ata,
+            &self.share_mint,
+            &self.underwriter_stake.to_account_info(),
+            &self.token_program,
+            shares,
+            Some(signer_seeds),
+        )?;
+        close_token_account(
+            &self.withdrawal_escrow,
+            &self.underwriter.to_account_info(),
+            &self.underwriter_stake.to_account_info(),
+            &self.token_program,
+            signer_seeds,
+        )?;
+
+        self.underwriter_stake.pending_shares = 0;
+        self.underwriter_stake.withdrawal_requested_at = 0;
+
+        emit!(WithdrawalCancelled {
+            pool_config: self.pool_config.key(),
+            underwriter: self.underwriter.key(),
+            shares,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use super::claim_protection::*;
+use crate::{
+    amm::{impermanent_loss, AmmConfig, Reserves, AMM_PROGRAM_ID},
+    error::ErrorCode,
+    release_lp_escrow, AmmOracle,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ClaimLpProtection<'info> {
+    pub protection: ClaimProtection<'info>,
+
+    #[account(
+        address = protection.policy.amm_config @ ErrorCode::NotLpPolicy,
+        seeds = [b"config", amm_config.seed.to_le_bytes().as_ref()],
+        seeds::program = AMM_PROGRAM_ID,
+        bump = amm_config.bump
+    )]
+    pub amm_config: Account<'info, AmmConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_oracle", protection.pool_config.key().as_ref(), amm_config.key().as_ref()],
+        bump = amm_oracle.bump
+    )]
+    pub amm_oracle: Account<'info, AmmOracle>,
+
+    #[account(
+        associated_token::mint = amm_config.mint_x,
+        associated_token::authority = amm_config,
+        associated_token::token_program = protection.token_program
+    )]
+    pub amm_vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        associated_token::mint = amm_config.mint_y,
+        associated_token::authority = amm_config,
+        associated_token::token_program = protection.token_program
+    )]
+    pub amm_vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"lp", amm_config.key().as_ref()],
+        seeds::program = AMM_PROGRAM_ID,
+        bump = amm_config.lp_bump
+    )]
+    pub mint_lp: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = protection.lp_owner,
+        associated_token::token_program = protection.token_program
+    )]
+    pub lp_owner_lp_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = protection.policy,
+        associated_token::token_program = protection.token_program
+    )]
+    pub lp_escrow: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> ClaimLpProtection<'info> {
+    pub fn claim_lp_protection(&mut self, settlement: &[AccountInfo<'info>]) -> Result<()> {
+        // The loss is measured on-chain, so these claims skip claim review.
+        let reserves = Reserves::new(
+            &self.amm_config,
+            self.amm_vault_x.amount,
+            self.amm_vault_y.amount,
+            self.protection.pool_mint.key(),
+        )?;
+        self.amm_oracle
+            .update(reserves.spot_price()?, Clock::get()?.unix_timestamp)?;
+
+        let policy = &self.protection.policy;
+        let (loss, loss_bps) = impermanent_loss(
+            &reserves,
+            self.amm_oracle.price,
+            policy.hold_x,
+            policy.hold_y,
+            policy.lp_amount,
+            self.mint_lp.supply,
+        )?;
+        require!(
+            loss_bps > policy.threshold as u64,
+            ErrorCode::LossBelowThreshold
+        );
+
+        let amount = loss.min(policy.coverage_amount);
+        self.protection
+            .settle(loss_bps as u16, amount, settlement)?;
+
+        release_lp_escrow(
+            &self.protection.policy,
+            &self.lp_escrow,
+            &self.lp_owner_lp_ata,
+            &self.mint_lp,
+            &self.protection.payer,
+            &self.protection.token_program,
+        )
+    }
+}
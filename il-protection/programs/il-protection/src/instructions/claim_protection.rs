@@ -1,4 +1,4 @@
-use crate::{error::ErrorCode, transfer_tokens, Policy, PoolConfig};
+use crate::{error::ErrorCode, ClaimPayout, Policy, PoolConfig};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
@@ -13,6 +13,7 @@ pub struct ClaimProtection<'info> {
         mut,
         has_one = pool_vault,
         has_one = pool_mint,
+        has_one = premium_vault,
         seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
         bump = pool_config.bump
     )]
@@ -34,6 +35,9 @@ pub struct ClaimProtection<'info> {
     )]
     pub pool_vault: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(mut, token::token_program = token_program)]
+    pub premium_vault: InterfaceAccount<'info, TokenAccount>,
+
     #[account(mint::token_program = token_program)]
     pub pool_mint: InterfaceAccount<'info, Mint>,
 
@@ -51,34 +55,53 @@ pub struct ClaimProtection<'info> {
 }
 
 impl<'info> ClaimProtection<'info> {
-    pub fn claim_protection(&mut self, threshold: u16) -> Result<()> {
+    pub fn claim_protection(
+        &mut self,
+        threshold: u16,
+        settlement: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        // AMM position policies settle on measured loss via claim_lp_protection.
+        require!(
+            self.policy.amm_config == Pubkey::default(),
+            ErrorCode::LpPolicy
+        );
         require!(
-            threshold > self.pool_config.threshold_max,
+            threshold > self.policy.threshold,
             ErrorCode::InvalidThreshold
         );
+        require!(
+            self.pool_config.challenge_window == 0
+                || self.policy.coverage_amount <= self.pool_config.auto_settle_limit,
+            ErrorCode::ClaimRequiresReview
+        );
 
-        self.pool_config.locked_shares = self
-            .pool_config
-            .locked_shares
-            .saturating_sub(self.policy.locked_shares);
-
-        self.transfer_amount(self.policy.coverage_amount)
+        self.settle(threshold, self.policy.coverage_amount, settlement)
     }
 
-    pub fn transfer_amount(&mut self, amount: u64) -> Result<()> {
-        let signer_seeds: &[&[&[u8]]] = &[&[
-            b"pool_config",
-            &self.pool_config.pool_id.to_le_bytes(),
-            &[self.pool_config.bump],
-        ]];
-        transfer_tokens(
-            &self.pool_vault,
-            &self.lp_owner_ata,
-            &self.pool_mint,
-            &self.pool_config.to_account_info(),
-            &self.token_program,
-            amount,
-            Some(signer_seeds),
-        )
+    /// Pays `amount` to the LP, releases the policy's shares and vests the
+    /// rest of its premium. The policy is closed by the accounts constraint.
+    pub fn settle(
+        &mut self,
+        threshold: u16,
+        amount: u64,
+        settlement: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(!self.policy.claim_pending, ErrorCode::ClaimPending);
+        require!(
+            Clock::get()?.unix_timestamp < self.policy.expiry_time,
+            ErrorCode::PolicyExpired
+        );
+
+        ClaimPayout {
+            pool_config: &mut self.pool_config,
+            policy: &mut self.policy,
+            pool_vault: &self.pool_vault,
+            premium_vault: &self.premium_vault,
+            pool_mint: &self.pool_mint,
+            lp_owner: &self.lp_owner,
+            lp_owner_ata: &self.lp_owner_ata,
+            token_program: &self.token_program,
+        }
+        .settle(threshold, amount, settlement)
     }
 }
@@ -0,0 +1,36 @@
+use crate::{error::ErrorCode, Claim, ClaimStatus, PoolConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseClaim<'info> {
+    #[account(mut)]
+    pub lp_owner: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        close = lp_owner,
+        has_one = pool_config,
+        has_one = lp_owner,
+        seeds = [b"claim", claim.policy.as_ref(), claim.nonce.to_le_bytes().as_ref()],
+        bump = claim.bump
+    )]
+    pub claim: Account<'info, Claim>,
+}
+
+impl<'info> CloseClaim<'info> {
+    /// Votes are escrowed per voter, so a resolved claim closes whether or
+    /// not they have been withdrawn.
+    pub fn close_claim(&mut self) -> Result<()> {
+        require!(
+            self.claim.status != ClaimStatus::Pending,
+            ErrorCode::ClaimPending
+        );
+        Ok(())
+    }
+}
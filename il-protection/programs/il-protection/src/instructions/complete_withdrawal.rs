@@ -0,0 +1,233 @@
+use crate::{
+    basket::{self, Settlement},
+    burn_tokens, close_token_account,
+    error::ErrorCode,
+    events::WithdrawalCompleted,
+    share_math, transfer_tokens, PoolConfig, UnderwriterStake, WITHDRAWAL_WINDOW,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct CompleteWithdrawal<'info> {
+    #[account(
+        mut,
+        has_one = pool_vault,
+        has_one = pool_mint,
+        has_one = share_mint,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = pool_config,
+        has_one = underwriter,
+        seeds = [b"underwriter", pool_config.pool_id.to_le_bytes().as_ref(), underwriter.key().as_ref()],
+        bump = underwriter_stake.bump
+    )]
+    pub underwriter_stake: Account<'info, UnderwriterStake>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = pool_config,
+        associated_token::token_program = token_program
+    )]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub underwriter: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = underwriter,
+        associated_token::token_program = token_program
+    )]
+    pub underwriter_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = underwriter,
+        associated_token::token_program = token_program
+    )]
+    pub underwriter_share_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = underwriter_stake,
+        associated_token::token_program = token_program
+    )]
+    pub withdrawal_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CompleteWithdrawal<'info> {
+    /// `remaining` holds the pool's basket, optionally followed by a
+    /// settlement to be paid in that basket asset instead of the pool mint.
+    pub fn complete_withdrawal(&mut self, remaining: &[AccountInfo<'info>]) -> Result<()> {
+        let withdraw_shares = self.underwriter_stake.pending_shares;
+        require!(withdraw_shares > 0, ErrorCode::NoPendingWithdrawal);
+
+        let unlocks_at = self
+            .underwriter_stake
+            .withdrawal_requested_at
+            .checked_add(self.pool_config.withdrawal_cooldown)
+            .ok_or(ErrorCode::Overflow)?;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= unlocks_at, ErrorCode::CooldownNotElapsed);
+        // Requests lapse so they cannot be armed ahead of a known claim.
+        require!(
+            now < unlocks_at.saturating_add(WITHDRAWAL_WINDOW),
+            ErrorCode::WithdrawalExpired
+        );
+
+        // Policies bought during the cooldown may have locked more of the pool.
+        let held = self
+            .underwriter_share_ata
+            .amount
+            .checked_add(self.withdrawal_escrow.amount)
+            .ok_or(ErrorCode::Overflow)?;
+        let unlocked_shares = share_math::unlocked_shares(
+            held,
+            self.pool_config.locked_shares,
+            self.pool_config.total_shares,
+        )?;
+        require!(
+            withdraw_shares <= unlocked_shares,
+            ErrorCode::NotEnoughShares
+        );
+
+        let basket_len = (2 * self.pool_config.collateral_assets as usize).min(remaining.len());
+        let (basket, settlement) = remaining.split_at(basket_len);
+        let pool_assets = basket::pool_assets(&self.pool_config, self.pool_vault.amount, basket)?;
+
+        // Queued shares are priced at completion, so premiums and claims paid
+        // during the cooldown are reflected in the amount received.
+        let value = share_math::assets_for_shares(
+            withdraw_shares,
+            self.pool_config.total_shares,
+            pool_assets,
+        )?;
+        // Only shares bought by staking have an entry price, so the others
+        // are left out of the realized gain rather than counted at zero cost.
+        let entry_share_price = self.underwriter_stake.entry_share_price;
+        let tracked_shares = self.underwriter_stake.remove_basis(held, withdraw_shares)?;
+        let tracked_value = share_math::assets_for_shares(
+            tracked_shares,
+            self.pool_config.total_shares,
+            pool_assets,
+        )?;
+        let settlement = if settlement.is_empty() {
+            None
+        } else {
+            Some(Settlement::load(
+                &self.pool_config.key(),
+                settlement,
+                &self.underwriter.key(),
+            )?)
+        };
+        // Basket assets are paid at face, grossed up for the haircut they
+        // were valued at.
+        let (mint, amount) = match &settlement {
+            Some(settlement) => (
+                settlement.asset.mint,
+                basket::face_amount(value, settlement.asset.haircut_bps)?,
+            ),
+            None => (self.pool_mint.key(), value),
+        };
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        self.pool_config.total_shares = self
+            .pool_config
+            .total_shares
+            .checked_sub(withdraw_shares)
+            .ok_or(ErrorCode::Overflow)?;
+
+        self.burn_shares(withdraw_shares)?;
+        match settlement {
+            Some(settlement) => settlement.pay(&self.pool_config, &self.token_program, amount)?,
+            None => self.transfer_amount(amount)?,
+        }
+
+        self.underwriter_stake.pending_shares = 0;
+        let pnl = share_math::pnl(tracked_value, tracked_shares, entry_share_price)?;
+        self.underwriter_stake.realized_pnl = self
+            .underwriter_stake
+            .realized_pnl
+            .checked_add(pnl)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(WithdrawalCompleted {
+            pool_config: self.pool_config.key(),
+            underwriter: self.underwriter.key(),
+            shares: withdraw_shares,
+            mint,
+            amount,
+            realized_pnl: pnl,
+            untracked_shares: withdraw_shares - tracked_shares,
+            total_shares: self.pool_config.total_shares,
+        });
+
+        Ok(())
+    }
+
+    pub fn transfer_amount(&mut self, amount: u64) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"pool_config",
+            &self.pool_config.pool_id.to_le_bytes(),
+            &[self.pool_config.bump],
+        ]];
+
+        transfer_tokens(
+            &self.pool_vault,
+            &self.underwriter_ata,
+            &self.pool_mint,
+            &self.pool_config.to_account_info(),
+            &self.token_program,
+            amount,
+            Some(signer_seeds),
+        )
+    }
+
+    /// Burns the escrowed shares and closes the escrow.
+    pub fn burn_shares(&mut self, shares: u64) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"underwriter",
+            &self.pool_config.pool_id.to_le_bytes(),
+            self.underwriter.key.as_ref(),
+            &[self.underwriter_stake.bump],
+        ]];
+        burn_tokens(
+            &self.share_mint,
+            &self.withdrawal_escrow,
+            &self.underwriter_stake.to_account_info(),
+            &self.token_program,
+            shares,
+            signer_seeds,
+        )?;
+        close_token_account(
+            &self.withdrawal_escrow,
+            &self.underwriter.to_account_info(),
+            &self.underwriter_stake.to_account_info(),
+            &self.token_program,
+            signer_seeds,
+        )
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{error::ErrorCode, events::PolicyExpired, vest_premium, Policy, PoolConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ExpirePolicy<'info> {
+    #[account(mut)]
+    pub lp_owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = pool_vault,
+        has_one = pool_mint,
+        has_one = premium_vault,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        close = lp_owner,
+        has_one = pool_config,
+        seeds = [b"policy", pool_config.key().as_ref(), lp_owner.key().as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = pool_config,
+        associated_token::token_program = token_program
+    )]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::token_program = token_program)]
+    pub premium_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ExpirePolicy<'info> {
+    pub fn expire_policy(&mut self) -> Result<()> {
+        // AMM position policies also return their escrow via expire_lp_policy.
+        require!(
+            self.policy.amm_config == Pubkey::default(),
+            ErrorCode::LpPolicy
+        );
+        self.expire()
+    }
+
+    pub fn expire(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= self.policy.expiry_time, ErrorCode::PolicyNotExpired);
+        require!(!self.policy.claim_pending, ErrorCode::ClaimPending);
+
+        vest_premium(
+            &mut self.pool_config,
+            &mut *self.policy,
+            &self.premium_vault,
+            &self.pool_vault,
+            &self.pool_mint,
+            &self.token_program,
+            now,
+        )?;
+
+        self.pool_config.locked_shares = self
+            .pool_config
+            .locked_shares
+            .saturating_sub(self.policy.locked_shares);
+
+        emit!(PolicyExpired {
+            pool_config: self.pool_config.key(),
+            policy: self.policy.key(),
+            lp_owner: self.lp_owner.key(),
+            premium: self.policy.premium,
+            locked_shares: self.policy.locked_shares,
+        });
+        Ok(())
+    }
+}
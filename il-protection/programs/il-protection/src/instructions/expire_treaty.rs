@@ -0,0 +1,95 @@
+use crate::{
+    error::ErrorCode, events::ReinsuranceExpired, pay_recoverable, vest_premium, PoolConfig,
+    ReinsuranceTreaty,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ExpireTreaty<'info> {
+    #[account(mut, address = primary_pool.admin)]
+    pub admin: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_config", primary_pool.pool_id.to_le_bytes().as_ref()],
+        bump = primary_pool.bump
+    )]
+    pub primary_pool: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = pool_mint,
+        seeds = [b"pool_config", reinsurer_pool.pool_id.to_le_bytes().as_ref()],
+        bump = reinsurer_pool.bump
+    )]
+    pub reinsurer_pool: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        close = admin,
+        has_one = primary_pool,
+        has_one = reinsurer_pool,
+        seeds = [b"treaty", primary_pool.key().as_ref(), reinsurer_pool.key().as_ref()],
+        bump = treaty.bump
+    )]
+    pub treaty: Account<'info, ReinsuranceTreaty>,
+
+    #[account(mut, address = primary_pool.pool_vault)]
+    pub primary_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = reinsurer_pool.pool_vault)]
+    pub reinsurer_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = reinsurer_pool.premium_vault)]
+    pub reinsurer_premium_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ExpireTreaty<'info> {
+    pub fn expire_treaty(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= self.treaty.expiry_time, ErrorCode::TreatyNotExpired);
+
+        vest_premium(
+            &mut self.reinsurer_pool,
+            &mut *self.treaty,
+            &self.reinsurer_premium_vault,
+            &self.reinsurer_vault,
+            &self.pool_mint,
+            &self.token_program,
+            now,
+        )?;
+        self.reinsurer_vault.reload()?;
+
+        // Losses the primary pool has paid are settled before the treaty
+        // closes, so expiring it never forfeits a recovery.
+        pay_recoverable(
+            &mut self.treaty,
+            &mut self.primary_pool,
+            &self.reinsurer_pool,
+            &self.primary_vault,
+            &self.reinsurer_vault,
+            &self.pool_mint,
+            &self.token_program,
+        )?;
+
+        self.reinsurer_pool.locked_shares = self
+            .reinsurer_pool
+            .locked_shares
+            .saturating_sub(self.treaty.locked_shares);
+
+        emit!(ReinsuranceExpired {
+            treaty: self.treaty.key(),
+            primary_pool: self.primary_pool.key(),
+            reinsurer_pool: self.reinsurer_pool.key(),
+            recovered: self.treaty.recovered,
+            locked_shares: self.treaty.locked_shares,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{error::ErrorCode, Claim, ClaimPayout, ClaimStatus, Policy, PoolConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct FinalizeClaim<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub lp_owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = pool_vault,
+        has_one = pool_mint,
+        has_one = premium_vault,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        close = lp_owner,
+        address = claim.policy,
+        seeds = [b"policy", pool_config.key().as_ref(), lp_owner.key().as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    #[account(
+        mut,
+        has_one = pool_config,
+        has_one = lp_owner,
+        seeds = [b"claim", claim.policy.as_ref(), claim.nonce.to_le_bytes().as_ref()],
+        bump = claim.bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = pool_config,
+        associated_token::token_program = token_program
+    )]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::token_program = token_program)]
+    pub premium_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = lp_owner,
+        associated_token::token_program = token_program
+    )]
+    pub lp_owner_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FinalizeClaim<'info> {
+    pub fn finalize_claim(&mut self, settlement: &[AccountInfo<'info>]) -> Result<()> {
+        require!(
+            self.claim.status == ClaimStatus::Pending,
+            ErrorCode::ClaimNotPending
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= self.claim.challenge_ends_at,
+            ErrorCode::ChallengeWindowOpen
+        );
+
+        self.claim.status = ClaimStatus::Paid;
+
+        ClaimPayout {
+            pool_config: &mut self.pool_config,
+            policy: &mut self.policy,
+            pool_vault: &self.pool_vault,
+            premium_vault: &self.premium_vault,
+            pool_mint: &self.pool_mint,
+            lp_owner: &self.lp_owner,
+            lp_owner_ata: &self.lp_owner_ata,
+            token_program: &self.token_program,
+        }
+        .settle(self.claim.threshold, self.claim.amount, settlement)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::PoolConfig;
+use crate::{error::ErrorCode, events::PoolInitialized, validate_basis_points, PoolConfig};
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
@@ -29,6 +29,28 @@ pub struct InitializePool<'info> {
     #[account(mint::token_program = token_program)]
     pub mint: InterfaceAccount<'info, Mint>,
 
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"premium_vault", pool_config.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool_config,
+        token::token_program = token_program
+    )]
+    pub premium_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"share_mint", pool_config.key().as_ref()],
+        bump,
+        mint::decimals = mint.decimals,
+        mint::authority = pool_config,
+        mint::token_program = token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
     #[account(mut)]
     pub signer: Signer<'info>,
 
@@ -45,18 +67,52 @@ impl<'info> InitializePool<'info> {
         pool_id: u64,
         premium_rate: u16,
         threshold_max: u16,
+        withdrawal_cooldown: i64,
+        cancellation_fee_bps: u16,
         bumps: &InitializePoolBumps,
     ) -> Result<()> {
+        validate_basis_points(premium_rate)?;
+        validate_basis_points(threshold_max)?;
+        validate_basis_points(cancellation_fee_bps)?;
+        require!(withdrawal_cooldown >= 0, ErrorCode::InvalidCooldown);
+
         self.pool_config.set_inner(PoolConfig {
             pool_id,
+            admin: self.signer.key(),
             premium_rate,
             total_shares: 0,
             locked_shares: 0,
+            claims_paid: 0,
+            reinsurance_recovered: 0,
             threshold_max,
             pool_vault: self.pool_vault.key(),
             pool_mint: self.mint.key(),
+            premium_vault: self.premium_vault.key(),
+            share_mint: self.share_mint.key(),
+            withdrawal_cooldown,
+            cancellation_fee_bps,
+            paused: false,
+            assessor: Pubkey::default(),
+            challenge_window: 0,
+            auto_settle_limit: 0,
+            collateral_assets: 0,
+            accrual_rate: 0,
+            premium_accrued: 0,
+            premium_accrued_at: 0,
+            share_bump: bumps.share_mint,
             bump: bumps.pool_config,
         });
+
+        emit!(PoolInitialized {
+            pool_config: self.pool_config.key(),
+            pool_id,
+            admin: self.signer.key(),
+            pool_mint: self.mint.key(),
+            premium_rate,
+            threshold_max,
+            withdrawal_cooldown,
+            cancellation_fee_bps,
+        });
         Ok(())
     }
 }
@@ -1,13 +1,71 @@
+pub mod add_amm_pool;
+pub mod add_collateral_asset;
+pub mod buy_lp_protection;
 pub mod buy_protection;
+pub mod buy_reinsurance;
+pub mod cancel_lp_policy;
+pub mod cancel_policy;
+pub mod cancel_withdrawal;
+pub mod claim_lp_protection;
 pub mod claim_protection;
+pub mod close_claim;
+pub mod complete_withdrawal;
+pub mod expire_lp_policy;
+pub mod expire_policy;
+pub mod expire_treaty;
+pub mod finalize_claim;
 pub mod initialize_pool;
+pub mod quote_premium;
+pub mod recover_reinsurance;
+pub mod reject_claim;
+pub mod release_premium;
+pub mod request_withdrawal;
+pub mod set_settlement_mint;
 pub mod shared;
+pub mod stake_basket_collateral;
 pub mod stake_collateral;
-pub mod withdraw_collateral;
+pub mod submit_claim;
+pub mod transfer_lp_policy;
+pub mod transfer_policy;
+pub mod underwriter_pnl;
+pub mod update_amm_oracle;
+pub mod update_collateral_asset;
+pub mod update_pool;
+pub mod vote_reject_claim;
+pub mod withdraw_vote;
 
+pub use add_amm_pool::*;
+pub use add_collateral_asset::*;
+pub use buy_lp_protection::*;
 pub use buy_protection::*;
+pub use buy_reinsurance::*;
+pub use cancel_lp_policy::*;
+pub use cancel_policy::*;
+pub use cancel_withdrawal::*;
+pub use claim_lp_protection::*;
 pub use claim_protection::*;
+pub use close_claim::*;
+pub use complete_withdrawal::*;
+pub use expire_lp_policy::*;
+pub use expire_policy::*;
+pub use expire_treaty::*;
+pub use finalize_claim::*;
 pub use initialize_pool::*;
+pub use quote_premium::*;
+pub use recover_reinsurance::*;
+pub use reject_claim::*;
+pub use release_premium::*;
+pub use request_withdrawal::*;
+pub use set_settlement_mint::*;
 pub use shared::*;
+pub use stake_basket_collateral::*;
 pub use stake_collateral::*;
-pub use withdraw_collateral::*;
+pub use submit_claim::*;
+pub use transfer_lp_policy::*;
+pub use transfer_policy::*;
+pub use underwriter_pnl::*;
+pub use update_amm_oracle::*;
+pub use update_collateral_asset::*;
+pub use update_pool::*;
+pub use vote_reject_claim::*;
+pub use withdraw_vote::*;
@@ -0,0 +1,41 @@
+use crate::{basket, pricing, share_math, PoolConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+#[derive(Accounts)]
+pub struct QuotePremium<'info> {
+    #[account(
+        has_one = pool_vault,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+impl<'info> QuotePremium<'info> {
+    /// Quotes as `buy_protection` would, pricing utilization after the cover
+    /// locks its shares.
+    pub fn quote_premium(
+        &self,
+        threshold: u16,
+        coverage_amount: u64,
+        duration: i64,
+        basket: &[AccountInfo<'info>],
+    ) -> Result<u64> {
+        let pool_assets = basket::pool_assets(&self.pool_config, self.pool_vault.amount, basket)?;
+        let locked_shares = share_math::shares_for_assets(
+            coverage_amount,
+            self.pool_config.total_shares,
+            pool_assets,
+        )?;
+        pricing::quote_premium(
+            &self.pool_config,
+            threshold,
+            coverage_amount,
+            duration,
+            locked_shares,
+        )
+    }
+}
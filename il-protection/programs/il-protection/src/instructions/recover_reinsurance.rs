@@ -0,0 +1,130 @@
+use crate::{
+    error::ErrorCode, events::ReinsuranceRecovered, transfer_tokens, vest_premium, PoolConfig,
+    ReinsuranceTreaty,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct RecoverReinsurance<'info> {
+    #[account(
+        mut,
+        has_one = pool_mint,
+        seeds = [b"pool_config", primary_pool.pool_id.to_le_bytes().as_ref()],
+        bump = primary_pool.bump
+    )]
+    pub primary_pool: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = pool_mint,
+        seeds = [b"pool_config", reinsurer_pool.pool_id.to_le_bytes().as_ref()],
+        bump = reinsurer_pool.bump
+    )]
+    pub reinsurer_pool: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = primary_pool,
+        has_one = reinsurer_pool,
+        seeds = [b"treaty", primary_pool.key().as_ref(), reinsurer_pool.key().as_ref()],
+        bump = treaty.bump
+    )]
+    pub treaty: Account<'info, ReinsuranceTreaty>,
+
+    #[account(mut, address = primary_pool.pool_vault)]
+    pub primary_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = reinsurer_pool.pool_vault)]
+    pub reinsurer_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, address = reinsurer_pool.premium_vault)]
+    pub reinsurer_premium_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RecoverReinsurance<'info> {
+    pub fn recover_reinsurance(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < self.treaty.expiry_time, ErrorCode::TreatyExpired);
+
+        // Also serves as the crank that pays the reinsurer its earned premium.
+        vest_premium(
+            &mut self.reinsurer_pool,
+            &mut *self.treaty,
+            &self.reinsurer_premium_vault,
+            &self.reinsurer_vault,
+            &self.pool_mint,
+            &self.token_program,
+            now,
+        )?;
+        self.reinsurer_vault.reload()?;
+
+        pay_recoverable(
+            &mut self.treaty,
+            &mut self.primary_pool,
+            &self.reinsurer_pool,
+            &self.primary_vault,
+            &self.reinsurer_vault,
+            &self.pool_mint,
+            &self.token_program,
+        )?;
+        Ok(())
+    }
+}
+
+/// Pays the primary pool what `treaty` owes it, as far as the reinsurer
+/// vault allows.
+pub fn pay_recoverable<'info>(
+    treaty: &mut Account<'info, ReinsuranceTreaty>,
+    primary_pool: &mut Account<'info, PoolConfig>,
+    reinsurer_pool: &Account<'info, PoolConfig>,
+    primary_vault: &InterfaceAccount<'info, TokenAccount>,
+    reinsurer_vault: &InterfaceAccount<'info, TokenAccount>,
+    pool_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<u64> {
+    let amount = treaty
+        .recoverable(primary_pool.claims_paid, primary_pool.reinsurance_recovered)
+        .min(reinsurer_vault.amount);
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    treaty.recovered = treaty
+        .recovered
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+    primary_pool.reinsurance_recovered = primary_pool
+        .reinsurance_recovered
+        .checked_add(amount)
+        .ok_or(ErrorCode::Overflow)?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"pool_config",
+        &reinsurer_pool.pool_id.to_le_bytes(),
+        &[reinsurer_pool.bump],
+    ]];
+    transfer_tokens(
+        reinsurer_vault,
+        primary_vault,
+        pool_mint,
+        &reinsurer_pool.to_account_info(),
+        token_program,
+        amount,
+        Some(signer_seeds),
+    )?;
+
+    emit!(ReinsuranceRecovered {
+        treaty: treaty.key(),
+        primary_pool: primary_pool.key(),
+        reinsurer_pool: reinsurer_pool.key(),
+        amount,
+        recovered: treaty.recovered,
+    });
+    Ok(amount)
+}
@@ -0,0 +1,42 @@
+use crate::{error::ErrorCode, Claim, ClaimStatus, Policy, PoolConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RejectClaim<'info> {
+    pub assessor: Signer<'info>,
+
+    #[account(
+        has_one = assessor @ ErrorCode::Unauthorized,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(mut, address = claim.policy)]
+    pub policy: Account<'info, Policy>,
+
+    #[account(
+        mut,
+        has_one = pool_config,
+        seeds = [b"claim", claim.policy.as_ref(), claim.nonce.to_le_bytes().as_ref()],
+        bump = claim.bump
+    )]
+    pub claim: Account<'info, Claim>,
+}
+
+impl<'info> RejectClaim<'info> {
+    pub fn reject_claim(&mut self) -> Result<()> {
+        require!(
+            self.claim.status == ClaimStatus::Pending,
+            ErrorCode::ClaimNotPending
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.claim.challenge_ends_at,
+            ErrorCode::ChallengeWindowClosed
+        );
+
+        self.claim.status = ClaimStatus::Rejected;
+        self.policy.claim_pending = false;
+        Ok(())
+    }
+}
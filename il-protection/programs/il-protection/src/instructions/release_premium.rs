@@ -0,0 +1,50 @@
+use crate::{vest_premium, Policy, PoolConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ReleasePremium<'info> {
+    #[account(
+        mut,
+        has_one = pool_vault,
+        has_one = pool_mint,
+        has_one = premium_vault,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(mut, has_one = pool_config)]
+    pub policy: Account<'info, Policy>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool_mint,
+        associated_token::authority = pool_config,
+        associated_token::token_program = token_program
+    )]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::token_program = token_program)]
+    pub premium_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub pool_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ReleasePremium<'info> {
+    pub fn release_premium(&mut self) -> Result<()> {
+        vest_premium(
+            &mut self.pool_config,
+            &mut *self.policy,
+            &self.premium_vault,
+            &self.pool_vault,
+            &self.pool_mint,
+            &self.token_program,
+            Clock::get()?.unix_timestamp,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{
+    error::ErrorCode, events::WithdrawalRequested, share_math, transfer_tokens, PoolConfig,
+    UnderwriterStake,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct RequestWithdrawal<'info> {
+    #[account(mut)]
+    pub underwriter: Signer<'info>,
+
+    #[account(
+        has_one = share_mint,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Share tokens are transferable, so holders who never staked need a stake
+    // account to queue a withdrawal.
+    #[account(
+        init_if_needed,
+        payer = underwriter,
+        space = UnderwriterStake::DISCRIMINATOR.len() + UnderwriterStake::INIT_SPACE,
+        seeds = [b"underwriter", pool_config.pool_id.to_le_bytes().as_ref(), underwriter.key().as_ref()],
+        bump
+    )]
+    pub underwriter_stake: Account<'info, UnderwriterStake>,
+
+    #[account(mint::token_program = token_program)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = underwriter,
+        associated_token::token_program = token_program
+    )]
+    pub underwriter_share_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // Queued shares sit here until the withdrawal completes or is cancelled,
+    // so they cannot be moved to another wallet's request.
+    #[account(
+        init_if_needed,
+        payer = underwriter,
+        associated_token::mint = share_mint,
+        associated_token::authority = underwriter_stake,
+        associated_token::token_program = token_program
+    )]
+    pub withdrawal_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RequestWithdrawal<'info> {
+    pub fn request_withdrawal(
+        &mut self,
+        shares: u64,
+        bumps: &RequestWithdrawalBumps,
+    ) -> Result<()> {
+        require!(shares > 0, ErrorCode::InvalidAmount);
+
+        let held = self
+            .underwriter_share_ata
+            .amount
+            .checked_add(self.withdrawal_escrow.amount)
+            .ok_or(ErrorCode::Overflow)?;
+        let unlocked_shares = share_math::unlocked_shares(
+            held,
+            self.pool_config.locked_shares,
+            self.pool_config.total_shares,
+        )?;
+        require!(shares <= unlocked_shares, ErrorCode::NotEnoughShares);
+
+        // A new request replaces any pending one and restarts the cooldown.
+        let requested_at = Clock::get()?.unix_timestamp;
+        if self.underwriter_stake.underwriter == Pubkey::default() {
+            self.underwriter_stake.set_inner(UnderwriterStake {
+                underwriter: self.underwriter.key(),
+                pool_config: self.pool_config.key(),
+                pending_shares: 0,
+                withdrawal_requested_at: 0,
+                entry_share_price: 0,
+                basis_shares: 0,
+                realized_pnl: 0,
+                bump: bumps.underwriter_stake,
+            });
+        }
+        self.escrow_shares(shares)?;
+        self.underwriter_stake.pending_shares = shares;
+        self.underwriter_stake.withdrawal_requested_at = requested_at;
+
+        emit!(WithdrawalRequested {
+            pool_config: self.pool_config.key(),
+            underwriter: self.underwriter.key(),
+            shares,
+            requested_at,
+        });
+
+        Ok(())
+    }
+
+    /// Moves shares between the holder and the escrow so that the escrow
+    /// holds exactly `shares`.
+    fn escrow_shares(&self, shares: u64) -> Result<()> {
+        let escrowed = self.withdrawal_escrow.amount;
+        if shares > escrowed {
+            return transfer_tokens(
+                &self.underwriter_share_ata,
+                &self.withdrawal_escrow,
+                &self.share_mint,
+                &self.underwriter.to_account_info(),
+                &self.token_program,
+                shares - escrowed,
+                None,
+            );
+        }
+        if shares < escrowed {
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"underwriter",
+                &self.pool_config.pool_id.to_le_bytes(),
+                self.underwriter.key.as_ref(),
+                &[self.underwriter_stake.bump],
+            ]];
+            return transfer_tokens(
+                &self.withdrawal_escrow,
+                &self.underwriter_share_ata,
+                &self.share_mint,
+                &self.underwriter_stake.to_account_info(),
+                &self.token_program,
+                escrowed - shares,
+                Some(signer_seeds),
+            );
+        }
+        Ok(())
+    }
+}
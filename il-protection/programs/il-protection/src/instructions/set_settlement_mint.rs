@@ -0,0 +1,44 @@
+use crate::{error::ErrorCode, CollateralAsset, Policy, PoolConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+pub struct SetSettlementMint<'info> {
+    pub lp_owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = pool_config,
+        seeds = [b"policy", pool_config.key().as_ref(), lp_owner.key().as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    pub settlement_mint: InterfaceAccount<'info, Mint>,
+
+    /// Required unless settling back in the pool mint.
+    #[account(
+        has_one = pool_config,
+        seeds = [b"collateral", pool_config.key().as_ref(), settlement_mint.key().as_ref()],
+        bump = collateral_asset.bump
+    )]
+    pub collateral_asset: Option<Account<'info, CollateralAsset>>,
+}
+
+impl<'info> SetSettlementMint<'info> {
+    pub fn set_settlement_mint(&mut self) -> Result<()> {
+        let mint = self.settlement_mint.key();
+        require!(
+            mint == self.pool_config.pool_mint || self.collateral_asset.is_some(),
+            ErrorCode::UnsupportedCollateral
+        );
+        self.policy.settlement_mint = mint;
+        Ok(())
+    }
+}
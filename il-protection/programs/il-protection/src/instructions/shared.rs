@@ -1,6 +1,14 @@
+use crate::{
+    basket::Settlement,
+    error::ErrorCode,
+    events::{ClaimLoss, ClaimPaid},
+    pricing::{vested_premium, PremiumSchedule},
+    share_math, Policy, PoolConfig, BPS_DENOMINATOR,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{
-    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+    burn_checked, close_account, mint_to_checked, transfer_checked, BurnChecked, CloseAccount,
+    Mint, MintToChecked, TokenAccount, TokenInterface, TransferChecked,
 };
 
 pub fn transfer_tokens<'info>(
@@ -28,3 +36,248 @@ pub fn transfer_tokens<'info>(
     }
     transfer_checked(cpi_ctx, amount, mint.decimals)
 }
+
+pub fn mint_tokens<'info>(
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = MintToChecked {
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+
+    let cpi_program = token_program.to_account_info();
+
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+    mint_to_checked(cpi_ctx, amount, mint.decimals)
+}
+
+pub fn burn_tokens<'info>(
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = BurnChecked {
+        mint: mint.to_account_info(),
+        from: from.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+
+    let cpi_program = token_program.to_account_info();
+
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+    burn_checked(cpi_ctx, amount, mint.decimals)
+}
+
+pub fn close_token_account<'info>(
+    account: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account: account.to_account_info(),
+        destination: destination.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+
+    let cpi_program = token_program.to_account_info();
+
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts).with_signer(signer_seeds);
+    close_account(cpi_ctx)
+}
+
+/// Accounts touched when a claim is paid, by `claim_protection`,
+/// `claim_lp_protection` and `finalize_claim`.
+pub struct ClaimPayout<'a, 'info> {
+    pub pool_config: &'a mut Account<'info, PoolConfig>,
+    pub policy: &'a mut Account<'info, Policy>,
+    pub pool_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub premium_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub pool_mint: &'a InterfaceAccount<'info, Mint>,
+    pub lp_owner: &'a AccountInfo<'info>,
+    pub lp_owner_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimPayout<'_, 'info> {
+    /// Pays `amount` to the LP, releases the policy's shares and vests the
+    /// rest of its premium. Closing the policy is left to the caller.
+    pub fn settle(
+        self,
+        threshold: u16,
+        amount: u64,
+        settlement: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        self.pool_config.locked_shares = self
+            .pool_config
+            .locked_shares
+            .saturating_sub(self.policy.locked_shares);
+
+        // A paid-out policy has earned its whole premium.
+        let expiry_time = self.policy.expiry_time;
+        vest_premium(
+            self.pool_config,
+            &mut **self.policy,
+            self.premium_vault,
+            self.pool_vault,
+            self.pool_mint,
+            self.token_program,
+            expiry_time,
+        )?;
+
+        self.pool_config.claims_paid = self
+            .pool_config
+            .claims_paid
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+
+        self.pay(amount, settlement)?;
+
+        emit!(ClaimPaid {
+            pool_config: self.pool_config.key(),
+            policy: self.policy.key(),
+            lp_owner: self.lp_owner.key(),
+            threshold,
+            mint: self.policy.settlement_mint,
+            amount,
+            locked_shares: self.policy.locked_shares,
+        });
+        emit!(ClaimLoss {
+            pool_config: self.pool_config.key(),
+            policy: self.policy.key(),
+            amount,
+            total_shares: self.pool_config.total_shares,
+            loss_per_share: share_math::per_share(amount, self.pool_config.total_shares)?,
+        });
+        Ok(())
+    }
+
+    /// Pays `amount` in the policy's settlement mint: from the pool vault, or
+    /// from the basket vault described by `settlement`.
+    fn pay(&self, amount: u64, settlement: &[AccountInfo<'info>]) -> Result<()> {
+        if self.policy.settlement_mint == self.pool_mint.key() {
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                b"pool_config",
+                &self.pool_config.pool_id.to_le_bytes(),
+                &[self.pool_config.bump],
+            ]];
+            return transfer_tokens(
+                self.pool_vault,
+                self.lp_owner_ata,
+                self.pool_mint,
+                &self.pool_config.to_account_info(),
+                self.token_program,
+                amount,
+                Some(signer_seeds),
+            );
+        }
+        let settlement =
+            Settlement::load(&self.pool_config.key(), settlement, &self.lp_owner.key())?;
+        require_keys_eq!(
+            settlement.asset.mint,
+            self.policy.settlement_mint,
+            ErrorCode::UnsupportedCollateral
+        );
+        settlement.pay(self.pool_config, self.token_program, amount)
+    }
+}
+
+/// Moves the LP tokens escrowed for `policy` to `destination` and closes the
+/// escrow, sending its rent to `rent_receiver`.
+pub fn release_lp_escrow<'info>(
+    policy: &Account<'info, Policy>,
+    lp_escrow: &InterfaceAccount<'info, TokenAccount>,
+    destination: &InterfaceAccount<'info, TokenAccount>,
+    mint_lp: &InterfaceAccount<'info, Mint>,
+    rent_receiver: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"policy",
+        policy.pool_config.as_ref(),
+        policy.lp_owner.as_ref(),
+        &[policy.bump],
+    ]];
+    transfer_tokens(
+        lp_escrow,
+        destination,
+        mint_lp,
+        &policy.to_account_info(),
+        token_program,
+        lp_escrow.amount,
+        Some(signer_seeds),
+    )?;
+    close_token_account(
+        lp_escrow,
+        rent_receiver,
+        &policy.to_account_info(),
+        token_program,
+        signer_seeds,
+    )
+}
+
+pub fn validate_basis_points(value: u16) -> Result<()> {
+    require!(
+        value as u64 <= BPS_DENOMINATOR,
+        ErrorCode::InvalidBasisPoints
+    );
+    Ok(())
+}
+
+/// Moves the part of `cover`'s premium earned by `now` from the premium vault
+/// into the pool vault, where it accrues to share holders.
+pub fn vest_premium<'info>(
+    pool_config: &mut Account<'info, PoolConfig>,
+    cover: &mut impl PremiumSchedule,
+    premium_vault: &InterfaceAccount<'info, TokenAccount>,
+    pool_vault: &InterfaceAccount<'info, TokenAccount>,
+    pool_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    now: i64,
+) -> Result<u64> {
+    let vested = vested_premium(
+        cover.premium(),
+        cover.start_time(),
+        cover.expiry_time(),
+        now,
+    )?;
+    let amount = vested.saturating_sub(cover.premium_vested());
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"pool_config",
+        &pool_config.pool_id.to_le_bytes(),
+        &[pool_config.bump],
+    ]];
+    transfer_tokens(
+        premium_vault,
+        pool_vault,
+        pool_mint,
+        &pool_config.to_account_info(),
+        token_program,
+        amount,
+        Some(signer_seeds),
+    )?;
+
+    // A cover stops accruing once its whole premium has been released.
+    let clock = Clock::get()?.unix_timestamp;
+    if vested == cover.premium() {
+        pool_config.remove_cover(cover, clock)?;
+    } else {
+        pool_config.release_premium(amount, clock)?;
+    }
+    cover.set_premium_vested(vested);
+    Ok(amount)
+}
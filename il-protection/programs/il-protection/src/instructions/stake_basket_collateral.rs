@@ -0,0 +1,174 @@
+use crate::error::ErrorCode;
+use crate::events::CollateralStaked;
+use crate::{
+    basket, mint_tokens, share_math, transfer_tokens, CollateralAsset, PoolConfig, UnderwriterStake,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct StakeBasketCollateral<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = pool_vault,
+        has_one = share_mint,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = UnderwriterStake::DISCRIMINATOR.len() + UnderwriterStake::INIT_SPACE,
+        seeds = [b"underwriter", pool_config.pool_id.to_le_bytes().as_ref(), underwriter.key().as_ref()],
+        bump
+    )]
+    pub underwriter_stake: Account<'info, UnderwriterStake>,
+
+    #[account(
+        has_one = pool_config,
+        seeds = [b"collateral", pool_config.key().as_ref(), asset_mint.key().as_ref()],
+        bump = collateral_asset.bump
+    )]
+    pub collateral_asset: Account<'info, CollateralAsset>,
+
+    #[account(mut, address = collateral_asset.vault)]
+    pub asset_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub asset_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(address = pool_config.pool_vault)]
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = pool_config.premium_vault)]
+    pub premium_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    pub underwriter: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = asset_mint,
+        associated_token::authority = underwriter,
+        associated_token::token_program = token_program
+    )]
+    pub underwriter_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = share_mint,
+        associated_token::authority = underwriter,
+        associated_token::token_program = token_program
+    )]
+    pub underwriter_share_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StakeBasketCollateral<'info> {
+    pub fn stake_basket_collateral(
+        &mut self,
+        amount: u64,
+        basket: &[AccountInfo<'info>],
+        bumps: &StakeBasketCollateralBumps,
+    ) -> Result<()> {
+        require!(!self.pool_config.paused, ErrorCode::PoolPaused);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        // Shares are minted for the deposit's haircut value.
+        let value = basket::haircut_value(amount, self.collateral_asset.haircut_bps)?;
+        let pool_assets = basket::staking_assets(
+            &self.pool_config,
+            self.pool_vault.amount,
+            self.premium_vault.amount,
+            basket,
+        )?;
+        let new_shares =
+            share_math::shares_for_deposit(value, self.pool_config.total_shares, pool_assets)?;
+
+        require!(new_shares > 0, ErrorCode::SharesZero);
+
+        self.pool_config.total_shares = self
+            .pool_config
+            .total_shares
+            .checked_add(new_shares)
+            .ok_or(ErrorCode::Overflow)?;
+
+        self.transfer_amount(amount)?;
+        self.mint_shares(new_shares)?;
+
+        if self.underwriter_stake.underwriter == Pubkey::default() {
+            self.underwriter_stake.set_inner(UnderwriterStake {
+                underwriter: self.underwriter.key(),
+                pool_config: self.pool_config.key(),
+                pending_shares: 0,
+                withdrawal_requested_at: 0,
+                entry_share_price: 0,
+                basis_shares: 0,
+                realized_pnl: 0,
+                bump: bumps.underwriter_stake,
+            });
+        }
+        // The share account still holds the balance from before this stake.
+        let held = self
+            .underwriter_share_ata
+            .amount
+            .checked_add(self.underwriter_stake.pending_shares)
+            .ok_or(ErrorCode::Overflow)?;
+        self.underwriter_stake.add_basis(held, new_shares, value)?;
+
+        emit!(CollateralStaked {
+            pool_config: self.pool_config.key(),
+            underwriter: self.underwriter.key(),
+            mint: self.asset_mint.key(),
+            amount,
+            shares: new_shares,
+            total_shares: self.pool_config.total_shares,
+        });
+        Ok(())
+    }
+
+    pub fn transfer_amount(&mut self, amount: u64) -> Result<()> {
+        transfer_tokens(
+            &self.underwriter_ata,
+            &self.asset_vault,
+            &self.asset_mint,
+            &self.underwriter,
+            &self.token_program,
+            amount,
+            None,
+        )
+    }
+
+    pub fn mint_shares(&mut self, shares: u64) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"pool_config",
+            &self.pool_config.pool_id.to_le_bytes(),
+            &[self.pool_config.bump],
+        ]];
+
+        mint_tokens(
+            &self.share_mint,
+            &self.underwriter_share_ata,
+            &self.pool_config.to_account_info(),
+            &self.token_program,
+            shares,
+            signer_seeds,
+        )
+    }
+}
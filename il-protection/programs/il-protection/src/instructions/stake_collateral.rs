@@ -1,7 +1,11 @@
 use crate::error::ErrorCode;
-use crate::{transfer_tokens, PoolConfig, UnderwriterStake};
+use crate::events::CollateralStaked;
+use crate::{basket, mint_tokens, share_math, transfer_tokens, PoolConfig, UnderwriterStake};
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 
 #[derive(Accounts)]
 pub struct StakeCollateral<'info> {
@@ -12,6 +16,8 @@ pub struct StakeCollateral<'info> {
         mut,
         has_one = pool_vault,
         has_one = pool_mint,
+        has_one = share_mint,
+        has_one = premium_vault,
         seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
         bump = pool_config.bump
     )]
@@ -34,9 +40,15 @@ pub struct StakeCollateral<'info> {
     )]
     pub pool_vault: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(token::token_program = token_program)]
+    pub premium_vault: InterfaceAccount<'info, TokenAccount>,
+
     #[account(mint::token_program = token_program)]
     pub pool_mint: InterfaceAccount<'info, Mint>,
 
+    #[account(mut, mint::token_program = token_program)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
     pub underwriter: Signer<'info>,
 
     #[account(
@@ -47,23 +59,39 @@ pub struct StakeCollateral<'info> {
     )]
     pub underwriter_ata: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = share_mint,
+        associated_token::authority = underwriter,
+        associated_token::token_program = token_program
+    )]
+    pub underwriter_share_ata: InterfaceAccount<'info, TokenAccount>,
+
     pub token_program: Interface<'info, TokenInterface>,
 
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> StakeCollateral<'info> {
-    pub fn stake_collateral(&mut self, amount: u64, bumps: &StakeCollateralBumps) -> Result<()> {
+    pub fn stake_collateral(
+        &mut self,
+        amount: u64,
+        basket: &[AccountInfo<'info>],
+        bumps: &StakeCollateralBumps,
+    ) -> Result<()> {
+        require!(!self.pool_config.paused, ErrorCode::PoolPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
-        let new_shares = if self.pool_config.total_shares == 0 {
-            amount
-        } else {
-            (amount as u128)
-                .checked_mul(self.pool_config.total_shares as u128)
-                .unwrap()
-                .checked_div(self.pool_vault.amount as u128)
-                .unwrap() as u64
-        };
+        let pool_assets = basket::staking_assets(
+            &self.pool_config,
+            self.pool_vault.amount,
+            self.premium_vault.amount,
+            basket,
+        )?;
+        let new_shares =
+            share_math::shares_for_deposit(amount, self.pool_config.total_shares, pool_assets)?;
 
         require!(new_shares > 0, ErrorCode::SharesZero);
 
@@ -74,20 +102,36 @@ impl<'info> StakeCollateral<'info> {
             .ok_or(ErrorCode::Overflow)?;
 
         self.transfer_amount(amount)?;
-        if self.underwriter_stake.shares == 0 {
+        self.mint_shares(new_shares)?;
+
+        if self.underwriter_stake.underwriter == Pubkey::default() {
             self.underwriter_stake.set_inner(UnderwriterStake {
                 underwriter: self.underwriter.key(),
                 pool_config: self.pool_config.key(),
-                shares: new_shares,
+                pending_shares: 0,
+                withdrawal_requested_at: 0,
+                entry_share_price: 0,
+                basis_shares: 0,
+                realized_pnl: 0,
                 bump: bumps.underwriter_stake,
             });
-        } else {
-            self.underwriter_stake.shares = self
-                .underwriter_stake
-                .shares
-                .checked_add(new_shares)
-                .ok_or(ErrorCode::Overflow)?;
         }
+        // The share account still holds the balance from before this stake.
+        let held = self
+            .underwriter_share_ata
+            .amount
+            .checked_add(self.underwriter_stake.pending_shares)
+            .ok_or(ErrorCode::Overflow)?;
+        self.underwriter_stake.add_basis(held, new_shares, amount)?;
+
+        emit!(CollateralStaked {
+            pool_config: self.pool_config.key(),
+            underwriter: self.underwriter.key(),
+            mint: self.pool_mint.key(),
+            amount,
+            shares: new_shares,
+            total_shares: self.pool_config.total_shares,
+        });
         Ok(())
     }
 
@@ -102,4 +146,21 @@ impl<'info> StakeCollateral<'info> {
             None,
         )
     }
+
+    pub fn mint_shares(&mut self, shares: u64) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"pool_config",
+            &self.pool_config.pool_id.to_le_bytes(),
+            &[self.pool_config.bump],
+        ]];
+
+        mint_tokens(
+            &self.share_mint,
+            &self.underwriter_share_ata,
+            &self.pool_config.to_account_info(),
+            &self.token_program,
+            shares,
+            signer_seeds,
+        )
+    }
 }
@@ -0,0 +1,87 @@
+use crate::{error::ErrorCode, Claim, ClaimStatus, Policy, PoolConfig, MAX_CLAIMS_PER_POLICY};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SubmitClaim<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub lp_owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"policy", pool_config.key().as_ref(), lp_owner.key().as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Claim::DISCRIMINATOR.len() + Claim::INIT_SPACE,
+        seeds = [
+            b"claim",
+            policy.key().as_ref(),
+            policy.claims_submitted.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitClaim<'info> {
+    pub fn submit_claim(&mut self, threshold: u16, bumps: &SubmitClaimBumps) -> Result<()> {
+        require!(
+            threshold > self.policy.threshold,
+            ErrorCode::InvalidThreshold
+        );
+        require!(
+            self.policy.amm_config == Pubkey::default(),
+            ErrorCode::LpPolicy
+        );
+        require!(!self.policy.claim_pending, ErrorCode::ClaimPending);
+        require!(
+            self.policy.claims_submitted < MAX_CLAIMS_PER_POLICY,
+            ErrorCode::ClaimLimitReached
+        );
+
+        let submitted_at = Clock::get()?.unix_timestamp;
+        require!(
+            submitted_at < self.policy.expiry_time,
+            ErrorCode::PolicyExpired
+        );
+        let challenge_ends_at = submitted_at
+            .checked_add(self.pool_config.challenge_window)
+            .ok_or(ErrorCode::Overflow)?;
+
+        // Each claim gets its own address, so resubmitting after a rejection
+        // never waits on the old claim being closed. Submissions are capped so
+        // that holders are not made to vote on the same policy indefinitely.
+        let nonce = self.policy.claims_submitted;
+        self.policy.claims_submitted = nonce.checked_add(1).ok_or(ErrorCode::Overflow)?;
+        self.policy.claim_pending = true;
+
+        self.claim.set_inner(Claim {
+            pool_config: self.pool_config.key(),
+            policy: self.policy.key(),
+            lp_owner: self.lp_owner.key(),
+            threshold,
+            amount: self.policy.coverage_amount,
+            submitted_at,
+            challenge_ends_at,
+            reject_votes: 0,
+            status: ClaimStatus::Pending,
+            nonce,
+            bump: bumps.claim,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use super::transfer_policy::*;
+use crate::{amm::AMM_PROGRAM_ID, error::ErrorCode, release_lp_escrow};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct TransferLpPolicy<'info> {
+    pub protection: TransferPolicy<'info>,
+
+    #[account(
+        seeds = [b"lp", protection.policy.amm_config.as_ref()],
+        seeds::program = AMM_PROGRAM_ID,
+        bump
+    )]
+    pub mint_lp: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_lp,
+        associated_token::authority = protection.policy,
+        associated_token::token_program = token_program
+    )]
+    pub lp_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    // The insured LP tokens move with the coverage.
+    #[account(
+        init,
+        payer = protection.payer,
+        associated_token::mint = mint_lp,
+        associated_token::authority = protection.new_policy,
+        associated_token::token_program = token_program
+    )]
+    pub new_lp_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TransferLpPolicy<'info> {
+    pub fn transfer_lp_policy(&mut self, bumps: &TransferLpPolicyBumps) -> Result<()> {
+        require!(
+            self.protection.policy.amm_config != Pubkey::default(),
+            ErrorCode::NotLpPolicy
+        );
+        self.protection.transfer(&bumps.protection)?;
+
+        release_lp_escrow(
+            &self.protection.policy,
+            &self.lp_escrow,
+            &self.new_lp_escrow,
+            &self.mint_lp,
+            &self.protection.lp_owner,
+            &self.token_program,
+        )
+    }
+}
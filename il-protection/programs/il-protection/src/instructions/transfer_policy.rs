@@ -0,0 +1,77 @@
+use crate::{error::ErrorCode, events::PolicyTransferred, Policy, PoolConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct TransferPolicy<'info> {
+    #[account(mut)]
+    pub lp_owner: Signer<'info>,
+
+    #[account(constraint = new_owner.key() != lp_owner.key() @ ErrorCode::SameOwner)]
+    pub new_owner: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        close = lp_owner,
+        has_one = pool_config,
+        has_one = lp_owner,
+        seeds = [b"policy", pool_config.key().as_ref(), lp_owner.key().as_ref()],
+        bump = policy.bump
+    )]
+    pub policy: Account<'info, Policy>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Policy::DISCRIMINATOR.len() + Policy::INIT_SPACE,
+        seeds = [b"policy", pool_config.key().as_ref(), new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_policy: Account<'info, Policy>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TransferPolicy<'info> {
+    pub fn transfer_policy(&mut self, bumps: &TransferPolicyBumps) -> Result<()> {
+        // AMM position policies also move their escrow via transfer_lp_policy.
+        require!(
+            self.policy.amm_config == Pubkey::default(),
+            ErrorCode::LpPolicy
+        );
+        self.transfer(bumps)
+    }
+
+    pub fn transfer(&mut self, bumps: &TransferPolicyBumps) -> Result<()> {
+        require!(!self.policy.claim_pending, ErrorCode::ClaimPending);
+        require!(
+            Clock::get()?.unix_timestamp < self.policy.expiry_time,
+            ErrorCode::PolicyExpired
+        );
+
+        // Policies are addressed by owner, so the coverage moves to the new
+        // owner's policy account and the old one is closed.
+        self.new_policy.set_inner(Policy {
+            lp_owner: self.new_owner.key(),
+            bump: bumps.new_policy,
+            ..self.policy.clone().into_inner()
+        });
+
+        emit!(PolicyTransferred {
+            pool_config: self.pool_config.key(),
+            from_policy: self.policy.key(),
+            to_policy: self.new_policy.key(),
+            from_owner: self.lp_owner.key(),
+            to_owner: self.new_owner.key(),
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{basket, error::ErrorCode, share_math, PoolConfig, UnderwriterStake};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// An underwriter's position, valued at the current share price.
+///
+/// The entry price is tracked per `UnderwriterStake`, not per share token, and
+/// only staking records one. Shares received by transfer have no known cost,
+/// so they are reported as `untracked_shares` and left out of the PnL instead
+/// of being counted at zero cost.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UnderwriterPnl {
+    pub shares: u64,            // held in the wallet or queued for withdrawal
+    pub untracked_shares: u64,  // part of `shares` with no entry price
+    pub entry_share_price: u64, // scaled by SHARE_PRICE_SCALE
+    pub share_price: u64,       // scaled by SHARE_PRICE_SCALE
+    pub unrealized_pnl: i64,    // tracked shares at share_price against their entry price
+    pub realized_pnl: i64,      // settled by completed withdrawals
+}
+
+#[derive(Accounts)]
+pub struct UnderwriterPnlView<'info> {
+    pub underwriter: SystemAccount<'info>,
+
+    #[account(
+        has_one = pool_vault,
+        has_one = share_mint,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        has_one = pool_config,
+        has_one = underwriter,
+        seeds = [b"underwriter", pool_config.pool_id.to_le_bytes().as_ref(), underwriter.key().as_ref()],
+        bump = underwriter_stake.bump
+    )]
+    pub underwriter_stake: Account<'info, UnderwriterStake>,
+
+    pub pool_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        associated_token::mint = share_mint,
+        associated_token::authority = underwriter,
+        associated_token::token_program = token_program
+    )]
+    pub underwriter_share_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> UnderwriterPnlView<'info> {
+    pub fn underwriter_pnl(&self, basket: &[AccountInfo<'info>]) -> Result<UnderwriterPnl> {
+        let pool_assets = basket::pool_assets(&self.pool_config, self.pool_vault.amount, basket)?;
+        let shares = self
+            .underwriter_share_ata
+            .amount
+            .checked_add(self.underwriter_stake.pending_shares)
+            .ok_or(ErrorCode::Overflow)?;
+        let tracked_shares = self.underwriter_stake.basis_held(shares);
+        let share_price = share_math::per_share(pool_assets, self.pool_config.total_shares)?;
+        let value = share_math::assets_for_shares(
+            tracked_shares,
+            self.pool_config.total_shares,
+            pool_assets,
+        )?;
+
+        Ok(UnderwriterPnl {
+            shares,
+            untracked_shares: shares - tracked_shares,
+            entry_share_price: self.underwriter_stake.entry_share_price,
+            share_price,
+            unrealized_pnl: share_math::pnl(
+                value,
+                tracked_shares,
+                self.underwriter_stake.entry_share_price,
+            )?,
+            realized_pnl: self.underwriter_stake.realized_pnl,
+        })
+    }
+}
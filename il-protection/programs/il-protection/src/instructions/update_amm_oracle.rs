@@ -0,0 +1,59 @@
+use crate::{
+    amm::{AmmConfig, Reserves, AMM_PROGRAM_ID},
+    AmmOracle, PoolConfig,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct UpdateAmmOracle<'info> {
+    #[account(
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        seeds = [b"config", amm_config.seed.to_le_bytes().as_ref()],
+        seeds::program = AMM_PROGRAM_ID,
+        bump = amm_config.bump
+    )]
+    pub amm_config: Account<'info, AmmConfig>,
+
+    #[account(
+        associated_token::mint = amm_config.mint_x,
+        associated_token::authority = amm_config,
+        associated_token::token_program = token_program
+    )]
+    pub amm_vault_x: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        associated_token::mint = amm_config.mint_y,
+        associated_token::authority = amm_config,
+        associated_token::token_program = token_program
+    )]
+    pub amm_vault_y: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_oracle", pool_config.key().as_ref(), amm_config.key().as_ref()],
+        bump = amm_oracle.bump
+    )]
+    pub amm_oracle: Account<'info, AmmOracle>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> UpdateAmmOracle<'info> {
+    /// Anyone can move the oracle towards the current spot price.
+    pub fn update_amm_oracle(&mut self) -> Result<()> {
+        let reserves = Reserves::new(
+            &self.amm_config,
+            self.amm_vault_x.amount,
+            self.amm_vault_y.amount,
+            self.pool_config.pool_mint,
+        )?;
+        self.amm_oracle
+            .update(reserves.spot_price()?, Clock::get()?.unix_timestamp)
+    }
+}
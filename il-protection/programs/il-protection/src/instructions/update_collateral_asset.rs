@@ -0,0 +1,43 @@
+use crate::{
+    error::ErrorCode, events::CollateralAssetConfigured, CollateralAsset, PoolConfig,
+    BPS_DENOMINATOR,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateCollateralAsset<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ ErrorCode::Unauthorized,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(
+        mut,
+        has_one = pool_config,
+        seeds = [b"collateral", pool_config.key().as_ref(), collateral_asset.mint.as_ref()],
+        bump = collateral_asset.bump
+    )]
+    pub collateral_asset: Account<'info, CollateralAsset>,
+}
+
+impl<'info> UpdateCollateralAsset<'info> {
+    pub fn update_collateral_asset(&mut self, haircut_bps: u16) -> Result<()> {
+        require!(
+            (haircut_bps as u64) < BPS_DENOMINATOR,
+            ErrorCode::InvalidHaircut
+        );
+        self.collateral_asset.haircut_bps = haircut_bps;
+
+        emit!(CollateralAssetConfigured {
+            pool_config: self.pool_config.key(),
+            mint: self.collateral_asset.mint,
+            vault: self.collateral_asset.vault,
+            haircut_bps,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{error::ErrorCode, validate_basis_points, PoolConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdatePool<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::Unauthorized,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+}
+
+impl<'info> UpdatePool<'info> {
+    pub fn update_params(
+        &mut self,
+        premium_rate: u16,
+        threshold_max: u16,
+        withdrawal_cooldown: i64,
+        cancellation_fee_bps: u16,
+    ) -> Result<()> {
+        validate_basis_points(premium_rate)?;
+        validate_basis_points(threshold_max)?;
+        validate_basis_points(cancellation_fee_bps)?;
+        require!(withdrawal_cooldown >= 0, ErrorCode::InvalidCooldown);
+
+        self.pool_config.premium_rate = premium_rate;
+        self.pool_config.threshold_max = threshold_max;
+        self.pool_config.withdrawal_cooldown = withdrawal_cooldown;
+        self.pool_config.cancellation_fee_bps = cancellation_fee_bps;
+        Ok(())
+    }
+
+    pub fn update_claims_config(
+        &mut self,
+        assessor: Pubkey,
+        challenge_window: i64,
+        auto_settle_limit: u64,
+    ) -> Result<()> {
+        require!(challenge_window >= 0, ErrorCode::InvalidChallengeWindow);
+
+        self.pool_config.assessor = assessor;
+        self.pool_config.challenge_window = challenge_window;
+        self.pool_config.auto_settle_limit = auto_settle_limit;
+        Ok(())
+    }
+
+    pub fn pause(&mut self) -> Result<()> {
+        require!(!self.pool_config.paused, ErrorCode::PoolPaused);
+        self.pool_config.paused = true;
+        Ok(())
+    }
+
+    pub fn unpause(&mut self) -> Result<()> {
+        require!(self.pool_config.paused, ErrorCode::PoolNotPaused);
+        self.pool_config.paused = false;
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{
+    error::ErrorCode, transfer_tokens, Claim, ClaimStatus, ClaimVote, Policy, PoolConfig,
+    BPS_DENOMINATOR, REJECT_QUORUM_BPS,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+#[derive(Accounts)]
+pub struct VoteRejectClaim<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        has_one = share_mint,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    #[account(mut, address = claim.policy)]
+    pub policy: Account<'info, Policy>,
+
+    #[account(
+        mut,
+        has_one = pool_config,
+        seeds = [b"claim", claim.policy.as_ref(), claim.nonce.to_le_bytes().as_ref()],
+        bump = claim.bump
+    )]
+    pub claim: Account<'info, Claim>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = ClaimVote::DISCRIMINATOR.len() + ClaimVote::INIT_SPACE,
+        seeds = [b"claim_vote", claim.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub claim_vote: Account<'info, ClaimVote>,
+
+    #[account(mint::token_program = token_program)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = voter,
+        associated_token::token_program = token_program
+    )]
+    pub voter_share_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // Escrows this voter's shares. It belongs to the vote rather than the
+    // claim, so voters withdraw without holding up the claim's close.
+    #[account(
+        init_if_needed,
+        payer = voter,
+        associated_token::mint = share_mint,
+        associated_token::authority = claim_vote,
+        associated_token::token_program = token_program
+    )]
+    pub vote_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> VoteRejectClaim<'info> {
+    pub fn vote_reject_claim(&mut self, shares: u64, bumps: &VoteRejectClaimBumps) -> Result<()> {
+        require!(shares > 0, ErrorCode::InvalidAmount);
+        require!(
+            self.claim.status == ClaimStatus::Pending,
+            ErrorCode::ClaimNotPending
+        );
+        require!(
+            Clock::get()?.unix_timestamp < self.claim.challenge_ends_at,
+            ErrorCode::ChallengeWindowClosed
+        );
+
+        // Votes are escrowed so the same shares cannot be moved and voted twice.
+        transfer_tokens(
+            &self.voter_share_ata,
+            &self.vote_escrow,
+            &self.share_mint,
+            &self.voter,
+            &self.token_program,
+            shares,
+            None,
+        )?;
+
+        if self.claim_vote.voter == Pubkey::default() {
+            self.claim_vote.set_inner(ClaimVote {
+                claim: self.claim.key(),
+                voter: self.voter.key(),
+                shares,
+                bump: bumps.claim_vote,
+            });
+        } else {
+            self.claim_vote.shares = self
+                .claim_vote
+                .shares
+                .checked_add(shares)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+
+        self.claim.reject_votes = self
+            .claim
+            .reject_votes
+            .checked_add(shares)
+            .ok_or(ErrorCode::Overflow)?;
+
+        let votes = (self.claim.reject_votes as u128) * BPS_DENOMINATOR as u128;
+        let quorum = (self.pool_config.total_shares as u128) * REJECT_QUORUM_BPS as u128;
+        if votes >= quorum {
+            self.claim.status = ClaimStatus::Rejected;
+            self.policy.claim_pending = false;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{
+    close_token_account, error::ErrorCode, transfer_tokens, Claim, ClaimStatus, ClaimVote,
+    PoolConfig,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct WithdrawVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        has_one = share_mint,
+        seeds = [b"pool_config", pool_config.pool_id.to_le_bytes().as_ref()],
+        bump = pool_config.bump
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    /// CHECK: the claim voted on; it may already have been closed
+    #[account(address = claim_vote.claim)]
+    pub claim: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = voter,
+        has_one = voter,
+        seeds = [b"claim_vote", claim_vote.claim.as_ref(), voter.key().as_ref()],
+        bump = claim_vote.bump
+    )]
+    pub claim_vote: Account<'info, ClaimVote>,
+
+    #[account(mint::token_program = token_program)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = voter,
+        associated_token::token_program = token_program
+    )]
+    pub voter_share_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = claim_vote,
+        associated_token::token_program = token_program
+    )]
+    pub vote_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawVote<'info> {
+    pub fn withdraw_vote(&mut self) -> Result<()> {
+        // A closed claim has been resolved.
+        if self.claim.owner == &crate::ID && !self.claim.data_is_empty() {
+            let claim = Claim::try_deserialize(&mut &self.claim.try_borrow_data()?[..])?;
+            require!(
+                claim.status != ClaimStatus::Pending
+                    || Clock::get()?.unix_timestamp >= claim.challenge_ends_at,
+                ErrorCode::ChallengeWindowOpen
+            );
+        }
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"claim_vote",
+            self.claim_vote.claim.as_ref(),
+            self.voter.key.as_ref(),
+            &[self.claim_vote.bump],
+        ]];
+
+        transfer_tokens(
+            &self.vote_escrow,
+            &self.voter_share_ata,
+            &self.share_mint,
+            &self.claim_vote.to_account_info(),
+            &self.token_program,
+            self.vote_escrow.amount,
+            Some(signer_seeds),
+        )?;
+        close_token_account(
+            &self.vote_escrow,
+            &self.voter.to_account_info(),
+            &self.claim_vote.to_account_info(),
+            &self.token_program,
+            signer_seeds,
+        )
+    }
+}
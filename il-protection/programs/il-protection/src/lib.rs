@@ -1,6 +1,11 @@
+pub mod amm;
+pub mod basket;
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod pricing;
+pub mod share_math;
 pub mod state;
 
 use anchor_lang::prelude::*;
@@ -20,30 +25,239 @@ pub mod il_protection {
         pool_id: u64,
         premium_rate: u16,
         threshold_max: u16,
+        withdrawal_cooldown: i64,
+        cancellation_fee_bps: u16,
     ) -> Result<()> {
-        ctx.accounts
-            .initialize_pool(pool_id, premium_rate, threshold_max, &ctx.bumps)
+        ctx.accounts.initialize_pool(
+            pool_id,
+            premium_rate,
+            threshold_max,
+            withdrawal_cooldown,
+            cancellation_fee_bps,
+            &ctx.bumps,
+        )
     }
 
-    pub fn buy_protection(
-        ctx: Context<BuyProtection>,
+    pub fn buy_protection<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyProtection<'info>>,
         threshold: u16,
         coverage_amount: u64,
         duration: i64,
     ) -> Result<()> {
+        ctx.accounts.buy_protection(
+            threshold,
+            coverage_amount,
+            duration,
+            ctx.remaining_accounts,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn buy_lp_protection<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyLpProtection<'info>>,
+        threshold: u16,
+        lp_amount: u64,
+        duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.buy_lp_protection(
+            threshold,
+            lp_amount,
+            duration,
+            ctx.remaining_accounts,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn quote_premium<'info>(
+        ctx: Context<'_, '_, '_, 'info, QuotePremium<'info>>,
+        threshold: u16,
+        coverage_amount: u64,
+        duration: i64,
+    ) -> Result<u64> {
         ctx.accounts
-            .buy_protection(threshold, coverage_amount, duration, &ctx.bumps)
+            .quote_premium(threshold, coverage_amount, duration, ctx.remaining_accounts)
+    }
+
+    pub fn underwriter_pnl<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnderwriterPnlView<'info>>,
+    ) -> Result<UnderwriterPnl> {
+        ctx.accounts.underwriter_pnl(ctx.remaining_accounts)
+    }
+
+    pub fn claim_protection<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimProtection<'info>>,
+        threshold: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .claim_protection(threshold, ctx.remaining_accounts)
+    }
+
+    pub fn claim_lp_protection<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimLpProtection<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.claim_lp_protection(ctx.remaining_accounts)
+    }
+
+    pub fn set_settlement_mint(ctx: Context<SetSettlementMint>) -> Result<()> {
+        ctx.accounts.set_settlement_mint()
     }
 
-    pub fn claim_protection(ctx: Context<ClaimProtection>, threshold: u16) -> Result<()> {
-        ctx.accounts.claim_protection(threshold)
+    pub fn release_premium(ctx: Context<ReleasePremium>) -> Result<()> {
+        ctx.accounts.release_premium()
     }
 
-    pub fn stake_collateral(ctx: Context<StakeCollateral>, amount: u64) -> Result<()> {
-        ctx.accounts.stake_collateral(amount, &ctx.bumps)
+    pub fn expire_policy(ctx: Context<ExpirePolicy>) -> Result<()> {
+        ctx.accounts.expire_policy()
     }
 
-    pub fn withdraw_collateral(ctx: Context<WithdrawCollateral>, amount: u64) -> Result<()> {
-        ctx.accounts.withdraw_collateral(amount)
+    pub fn expire_lp_policy(ctx: Context<ExpireLpPolicy>) -> Result<()> {
+        ctx.accounts.expire_lp_policy()
+    }
+
+    pub fn cancel_policy(ctx: Context<CancelPolicy>) -> Result<()> {
+        ctx.accounts.cancel_policy()
+    }
+
+    pub fn cancel_lp_policy(ctx: Context<CancelLpPolicy>) -> Result<()> {
+        ctx.accounts.cancel_lp_policy()
+    }
+
+    pub fn transfer_policy(ctx: Context<TransferPolicy>) -> Result<()> {
+        ctx.accounts.transfer_policy(&ctx.bumps)
+    }
+
+    pub fn transfer_lp_policy(ctx: Context<TransferLpPolicy>) -> Result<()> {
+        ctx.accounts.transfer_lp_policy(&ctx.bumps)
+    }
+
+    pub fn submit_claim(ctx: Context<SubmitClaim>, threshold: u16) -> Result<()> {
+        ctx.accounts.submit_claim(threshold, &ctx.bumps)
+    }
+
+    pub fn vote_reject_claim(ctx: Context<VoteRejectClaim>, shares: u64) -> Result<()> {
+        ctx.accounts.vote_reject_claim(shares, &ctx.bumps)
+    }
+
+    pub fn reject_claim(ctx: Context<RejectClaim>) -> Result<()> {
+        ctx.accounts.reject_claim()
+    }
+
+    pub fn finalize_claim<'info>(
+        ctx: Context<'_, '_, '_, 'info, FinalizeClaim<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.finalize_claim(ctx.remaining_accounts)
+    }
+
+    pub fn withdraw_vote(ctx: Context<WithdrawVote>) -> Result<()> {
+        ctx.accounts.withdraw_vote()
+    }
+
+    pub fn close_claim(ctx: Context<CloseClaim>) -> Result<()> {
+        ctx.accounts.close_claim()
+    }
+
+    pub fn buy_reinsurance<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyReinsurance<'info>>,
+        attachment: u64,
+        limit: u64,
+        duration: i64,
+    ) -> Result<()> {
+        ctx.accounts.buy_reinsurance(
+            attachment,
+            limit,
+            duration,
+            ctx.remaining_accounts,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn recover_reinsurance(ctx: Context<RecoverReinsurance>) -> Result<()> {
+        ctx.accounts.recover_reinsurance()
+    }
+
+    pub fn expire_treaty(ctx: Context<ExpireTreaty>) -> Result<()> {
+        ctx.accounts.expire_treaty()
+    }
+
+    pub fn stake_collateral<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeCollateral<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .stake_collateral(amount, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn stake_basket_collateral<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeBasketCollateral<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .stake_basket_collateral(amount, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn add_collateral_asset(ctx: Context<AddCollateralAsset>, haircut_bps: u16) -> Result<()> {
+        ctx.accounts.add_collateral_asset(haircut_bps, &ctx.bumps)
+    }
+
+    pub fn add_amm_pool(ctx: Context<AddAmmPool>) -> Result<()> {
+        ctx.accounts.add_amm_pool(&ctx.bumps)
+    }
+
+    pub fn update_amm_oracle(ctx: Context<UpdateAmmOracle>) -> Result<()> {
+        ctx.accounts.update_amm_oracle()
+    }
+
+    pub fn update_collateral_asset(
+        ctx: Context<UpdateCollateralAsset>,
+        haircut_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.update_collateral_asset(haircut_bps)
+    }
+
+    pub fn update_params(
+        ctx: Context<UpdatePool>,
+        premium_rate: u16,
+        threshold_max: u16,
+        withdrawal_cooldown: i64,
+        cancellation_fee_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.update_params(
+            premium_rate,
+            threshold_max,
+            withdrawal_cooldown,
+            cancellation_fee_bps,
+        )
+    }
+
+    pub fn update_claims_config(
+        ctx: Context<UpdatePool>,
+        assessor: Pubkey,
+        challenge_window: i64,
+        auto_settle_limit: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .update_claims_config(assessor, challenge_window, auto_settle_limit)
+    }
+
+    pub fn pause(ctx: Context<UpdatePool>) -> Result<()> {
+        ctx.accounts.pause()
+    }
+
+    pub fn unpause(ctx: Context<UpdatePool>) -> Result<()> {
+        ctx.accounts.unpause()
+    }
+
+    pub fn request_withdrawal(ctx: Context<RequestWithdrawal>, shares: u64) -> Result<()> {
+        ctx.accounts.request_withdrawal(shares, &ctx.bumps)
+    }
+
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        ctx.accounts.cancel_withdrawal()
+    }
+
+    pub fn complete_withdrawal<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompleteWithdrawal<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.complete_withdrawal(ctx.remaining_accounts)
     }
 }
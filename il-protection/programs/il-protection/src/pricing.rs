@@ -0,0 +1,178 @@
+use crate::{
+    error::ErrorCode, PoolConfig, BPS_DENOMINATOR, PREMIUM_ACCRUAL_SCALE, PREMIUM_PERIOD,
+    UTILIZATION_JUMP_BPS, UTILIZATION_KINK_BPS, UTILIZATION_SLOPE_BPS,
+};
+use anchor_lang::prelude::*;
+
+/// Premium for a policy, in pool mint units.
+///
+/// premium = coverage × premium_rate × risk × utilization × duration / 30 days
+///
+/// Utilization is taken after the cover's `locked_shares` are added, so a
+/// purchase pays for the utilization it creates.
+pub fn quote_premium(
+    pool_config: &PoolConfig,
+    threshold: u16,
+    coverage_amount: u64,
+    duration: i64,
+    locked_shares: u64,
+) -> Result<u64> {
+    require!(
+        threshold <= pool_config.threshold_max,
+        ErrorCode::InvalidThreshold
+    );
+    require!(coverage_amount > 0, ErrorCode::InvalidAmount);
+    require!(duration > 0, ErrorCode::InvalidDuration);
+
+    let risk = threshold_multiplier(threshold, pool_config.threshold_max);
+    let utilization = utilization_multiplier(utilization_bps(
+        pool_config.locked_shares.saturating_add(locked_shares),
+        pool_config.total_shares,
+    ));
+
+    let bps = BPS_DENOMINATOR as u128;
+    let premium = (coverage_amount as u128)
+        .checked_mul(pool_config.premium_rate as u128)
+        .and_then(|v| v.checked_mul(risk as u128))
+        .and_then(|v| v.checked_mul(utilization as u128))
+        .and_then(|v| v.checked_mul(duration as u128))
+        .and_then(|v| v.checked_div(bps * bps * bps * PREMIUM_PERIOD as u128))
+        .ok_or(ErrorCode::Overflow)?;
+
+    u64::try_from(premium).map_err(|_| ErrorCode::Overflow.into())
+}
+
+/// Part of `premium` earned by `now`, vesting linearly from `start_time`
+/// to `expiry_time`.
+pub fn vested_premium(premium: u64, start_time: i64, expiry_time: i64, now: i64) -> Result<u64> {
+    if now >= expiry_time {
+        return Ok(premium);
+    }
+    if now <= start_time {
+        return Ok(0);
+    }
+    let elapsed = (now - start_time) as u128;
+    let duration = (expiry_time - start_time) as u128;
+    let vested = (premium as u128)
+        .checked_mul(elapsed)
+        .and_then(|v| v.checked_div(duration))
+        .ok_or(ErrorCode::Overflow)?;
+    u64::try_from(vested).map_err(|_| ErrorCode::Overflow.into())
+}
+
+/// Premium `cover` earns per second, scaled by `PREMIUM_ACCRUAL_SCALE`.
+pub fn premium_accrual_rate(cover: &impl PremiumSchedule) -> u128 {
+    let duration = cover.expiry_time().saturating_sub(cover.start_time());
+    if duration <= 0 {
+        return 0;
+    }
+    cover.premium() as u128 * PREMIUM_ACCRUAL_SCALE / duration as u128
+}
+
+/// Cover whose premium sits in a premium vault and vests over its term.
+pub trait PremiumSchedule {
+    fn premium(&self) -> u64;
+    fn premium_vested(&self) -> u64;
+    fn set_premium_vested(&mut self, premium_vested: u64);
+    fn start_time(&self) -> i64;
+    fn expiry_time(&self) -> i64;
+}
+
+/// Lower thresholds pay out more often, so they cost more:
+/// 2x at a threshold of zero, 1x at `threshold_max`.
+pub fn threshold_multiplier(threshold: u16, threshold_max: u16) -> u64 {
+    if threshold_max == 0 {
+        return BPS_DENOMINATOR;
+    }
+    let discount = (threshold_max.saturating_sub(threshold)) as u64;
+    BPS_DENOMINATOR + discount * BPS_DENOMINATOR / threshold_max as u64
+}
+
+/// Share of the pool already backing policies, in basis points.
+pub fn utilization_bps(locked_shares: u64, total_shares: u64) -> u64 {
+    if total_shares == 0 {
+        return 0;
+    }
+    let utilization = (locked_shares as u128) * (BPS_DENOMINATOR as u128) / total_shares as u128;
+    utilization.min(BPS_DENOMINATOR as u128) as u64
+}
+
+/// Kinked curve: gentle up to `UTILIZATION_KINK_BPS`, steep after it so the
+/// last free collateral is expensive.
+pub fn utilization_multiplier(utilization_bps: u64) -> u64 {
+    if utilization_bps <= UTILIZATION_KINK_BPS {
+        BPS_DENOMINATOR + utilization_bps * UTILIZATION_SLOPE_BPS / UTILIZATION_KINK_BPS
+    } else {
+        let excess = utilization_bps - UTILIZATION_KINK_BPS;
+        BPS_DENOMINATOR
+            + UTILIZATION_SLOPE_BPS
+            + excess * UTILIZATION_JUMP_BPS / (BPS_DENOMINATOR - UTILIZATION_KINK_BPS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_pool;
+
+    fn pool(locked_shares: u64, total_shares: u64) -> PoolConfig {
+        PoolConfig {
+            locked_shares,
+            total_shares,
+            ..test_pool()
+        }
+    }
+
+    #[test]
+    fn threshold_at_max_on_idle_pool_matches_flat_rate() {
+        let premium =
+            quote_premium(&pool(0, 1_000), 2_000, 100_000_000, PREMIUM_PERIOD, 0).unwrap();
+        assert_eq!(premium, 5_000_000);
+    }
+
+    #[test]
+    fn lower_threshold_and_higher_utilization_cost_more() {
+        let idle = pool(0, 1_000);
+        let busy = pool(900, 1_000);
+        let base = quote_premium(&idle, 2_000, 100_000_000, PREMIUM_PERIOD, 0).unwrap();
+        let risky = quote_premium(&idle, 500, 100_000_000, PREMIUM_PERIOD, 0).unwrap();
+        let utilized = quote_premium(&busy, 2_000, 100_000_000, PREMIUM_PERIOD, 0).unwrap();
+        assert!(risky > base);
+        assert!(utilized > base);
+        assert_eq!(threshold_multiplier(0, 2_000), 2 * BPS_DENOMINATOR);
+        assert_eq!(utilization_multiplier(BPS_DENOMINATOR), 45_000);
+    }
+
+    #[test]
+    fn large_purchase_pays_the_utilization_it_creates() {
+        let idle = pool(0, 1_000);
+        let small = quote_premium(&idle, 2_000, 100_000_000, PREMIUM_PERIOD, 10).unwrap();
+        let whole = quote_premium(&idle, 2_000, 100_000_000, PREMIUM_PERIOD, 1_000).unwrap();
+        // 1% utilization after the purchase
+        assert_eq!(small, 5_031_000);
+        // locking the whole pool prices at 100% utilization (4.5x)
+        assert_eq!(whole, 22_500_000);
+        // the same as buying into a pool that is already fully locked
+        let full = quote_premium(&pool(1_000, 1_000), 2_000, 100_000_000, PREMIUM_PERIOD, 0);
+        assert_eq!(whole, full.unwrap());
+    }
+
+    #[test]
+    fn premium_vests_linearly_over_the_policy() {
+        assert_eq!(vested_premium(1_000, 100, 200, 50).unwrap(), 0);
+        assert_eq!(vested_premium(1_000, 100, 200, 100).unwrap(), 0);
+        assert_eq!(vested_premium(1_000, 100, 200, 125).unwrap(), 250);
+        assert_eq!(vested_premium(1_000, 100, 200, 199).unwrap(), 990);
+        assert_eq!(vested_premium(1_000, 100, 200, 200).unwrap(), 1_000);
+        assert_eq!(vested_premium(1_000, 100, 200, 10_000).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        let pool = pool(0, 1_000);
+        assert!(quote_premium(&pool, 2_001, 1, PREMIUM_PERIOD, 0).is_err());
+        assert!(quote_premium(&pool, 1_000, 0, PREMIUM_PERIOD, 0).is_err());
+        assert!(quote_premium(&pool, 1_000, 1, 0, 0).is_err());
+        assert!(quote_premium(&pool, 1_000, 1, -1, 0).is_err());
+    }
+}
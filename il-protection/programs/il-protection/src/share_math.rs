@@ -0,0 +1,185 @@
+use crate::{error::ErrorCode, SHARE_PRICE_SCALE};
+use anchor_lang::prelude::*;
+
+/// Shares minted for depositing `amount`, rounded down.
+///
+/// The first deposit (no shares outstanding) mints 1:1, and that depositor
+/// also picks up anything left in the vault.
+pub fn shares_for_deposit(amount: u64, total_shares: u64, pool_assets: u64) -> Result<u64> {
+    if total_shares == 0 {
+        return Ok(amount);
+    }
+    require!(pool_assets > 0, ErrorCode::PoolInsolvent);
+    mul_div_floor(amount, total_shares, pool_assets)
+}
+
+/// Assets paid out for burning `shares`, rounded down.
+pub fn assets_for_shares(shares: u64, total_shares: u64, pool_assets: u64) -> Result<u64> {
+    require!(total_shares > 0, ErrorCode::EmptyPool);
+    require!(shares <= total_shares, ErrorCode::NotEnoughShares);
+    mul_div_floor(shares, pool_assets, total_shares)
+}
+
+/// Shares worth `amount` of pool assets, rounded up.
+pub fn shares_for_assets(amount: u64, total_shares: u64, pool_assets: u64) -> Result<u64> {
+    require!(total_shares > 0, ErrorCode::EmptyPool);
+    require!(pool_assets > 0, ErrorCode::PoolInsolvent);
+    mul_div_ceil(amount, total_shares, pool_assets)
+}
+
+/// Part of `shares` that is free of active policies. The locked part is
+/// rounded up so a holder can never withdraw collateral backing a policy.
+pub fn unlocked_shares(shares: u64, locked_shares: u64, total_shares: u64) -> Result<u64> {
+    if total_shares == 0 {
+        return Ok(0);
+    }
+    let locked = mul_div_ceil(locked_shares, shares, total_shares)?;
+    Ok(shares.saturating_sub(locked))
+}
+
+/// `amount` per share, scaled by `SHARE_PRICE_SCALE` and rounded down.
+pub fn per_share(amount: u64, total_shares: u64) -> Result<u64> {
+    require!(total_shares > 0, ErrorCode::EmptyPool);
+    mul_div_floor(amount, SHARE_PRICE_SCALE, total_shares)
+}
+
+/// Average entry price after buying `new_shares` for `cost` on top of
+/// `held_shares` bought at `entry_price`.
+pub fn average_entry_price(
+    held_shares: u64,
+    entry_price: u64,
+    new_shares: u64,
+    cost: u64,
+) -> Result<u64> {
+    let shares = held_shares
+        .checked_add(new_shares)
+        .ok_or(ErrorCode::Overflow)?;
+    require!(shares > 0, ErrorCode::SharesZero);
+    let value = (held_shares as u128)
+        .checked_mul(entry_price as u128)
+        .and_then(|v| v.checked_add(cost as u128 * SHARE_PRICE_SCALE as u128))
+        .and_then(|v| v.checked_div(shares as u128))
+        .ok_or(ErrorCode::Overflow)?;
+    u64::try_from(value).map_err(|_| ErrorCode::Overflow.into())
+}
+
+/// Gain (or loss, if negative) on `shares` worth `value` that were bought at
+/// `entry_price`.
+pub fn pnl(value: u64, shares: u64, entry_price: u64) -> Result<i64> {
+    let cost = (shares as u128) * (entry_price as u128) / SHARE_PRICE_SCALE as u128;
+    i64::try_from(value as i128 - cost as i128).map_err(|_| ErrorCode::Overflow.into())
+}
+
+fn mul_div_floor(a: u64, b: u64, denominator: u64) -> Result<u64> {
+    let value = (a as u128)
+        .checked_mul(b as u128)
+        .and_then(|v| v.checked_div(denominator as u128))
+        .ok_or(ErrorCode::Overflow)?;
+    u64::try_from(value).map_err(|_| ErrorCode::Overflow.into())
+}
+
+fn mul_div_ceil(a: u64, b: u64, denominator: u64) -> Result<u64> {
+    require!(denominator > 0, ErrorCode::Overflow);
+    let value = (a as u128)
+        .checked_mul(b as u128)
+        .and_then(|v| v.checked_add(denominator as u128 - 1))
+        .and_then(|v| v.checked_div(denominator as u128))
+        .ok_or(ErrorCode::Overflow)?;
+    u64::try_from(value).map_err(|_| ErrorCode::Overflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_deposit_mints_one_to_one() {
+        assert_eq!(shares_for_deposit(100, 0, 0).unwrap(), 100);
+        // leftover premiums with no shares outstanding go to the first depositor
+        assert_eq!(shares_for_deposit(100, 0, 50).unwrap(), 100);
+    }
+
+    #[test]
+    fn deposit_into_drained_pool_fails() {
+        assert_eq!(
+            shares_for_deposit(100, 1_000, 0).unwrap_err(),
+            ErrorCode::PoolInsolvent.into()
+        );
+        assert_eq!(
+            shares_for_assets(100, 1_000, 0).unwrap_err(),
+            ErrorCode::PoolInsolvent.into()
+        );
+    }
+
+    #[test]
+    fn empty_pool_has_nothing_to_redeem_or_lock() {
+        assert_eq!(
+            assets_for_shares(1, 0, 100).unwrap_err(),
+            ErrorCode::EmptyPool.into()
+        );
+        assert_eq!(
+            shares_for_assets(1, 0, 100).unwrap_err(),
+            ErrorCode::EmptyPool.into()
+        );
+        assert_eq!(unlocked_shares(0, 0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn rounding_favors_the_pool() {
+        // 3 shares backed by 10 assets
+        assert_eq!(shares_for_deposit(5, 3, 10).unwrap(), 1);
+        assert_eq!(assets_for_shares(1, 3, 10).unwrap(), 3);
+        assert_eq!(shares_for_assets(4, 3, 10).unwrap(), 2);
+        // 1 of 3 shares locked: a holder of 2 shares has 2/3 locked -> 1 locked
+        assert_eq!(unlocked_shares(2, 1, 3).unwrap(), 1);
+    }
+
+    #[test]
+    fn round_trip_never_creates_value() {
+        let (total_shares, pool_assets) = (999_983, 1_000_037);
+        for amount in [1, 7, 1_000, 123_457] {
+            let shares = shares_for_deposit(amount, total_shares, pool_assets).unwrap();
+            let out =
+                assets_for_shares(shares, total_shares + shares, pool_assets + amount).unwrap();
+            assert!(out <= amount);
+        }
+    }
+
+    #[test]
+    fn redeeming_more_than_supply_fails() {
+        assert_eq!(
+            assets_for_shares(11, 10, 100).unwrap_err(),
+            ErrorCode::NotEnoughShares.into()
+        );
+    }
+
+    #[test]
+    fn tracks_entry_price_and_pnl() {
+        // 100 shares at 1.0, then 50 more for 60 assets (1.2 each)
+        let entry = average_entry_price(0, 0, 100, 100).unwrap();
+        assert_eq!(entry, SHARE_PRICE_SCALE);
+        let entry = average_entry_price(100, entry, 50, 60).unwrap();
+        assert_eq!(entry, 1_066_666_666);
+        // a claim pushes the price down to 0.9
+        let price = per_share(135, 150).unwrap();
+        assert_eq!(price, 900_000_000);
+        assert_eq!(pnl(90, 100, entry).unwrap(), -16);
+        assert_eq!(pnl(120, 100, SHARE_PRICE_SCALE).unwrap(), 20);
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        assert_eq!(
+            shares_for_deposit(u64::MAX, u64::MAX, 1).unwrap_err(),
+            ErrorCode::Overflow.into()
+        );
+        assert_eq!(
+            shares_for_assets(u64::MAX, u64::MAX, 1).unwrap_err(),
+            ErrorCode::Overflow.into()
+        );
+        assert_eq!(
+            assets_for_shares(u64::MAX, u64::MAX, u64::MAX).unwrap(),
+            u64::MAX
+        );
+    }
+}
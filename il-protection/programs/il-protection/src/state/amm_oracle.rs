@@ -0,0 +1,94 @@
+use crate::{error::ErrorCode, BPS_DENOMINATOR, ORACLE_MAX_MOVE_BPS, ORACLE_MOVE_BPS_PER_SECOND};
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct AmmOracle {
+    pub pool_config: Pubkey, // pool insuring positions in the AMM pool
+    pub amm_config: Pubkey,  // amm-program pool being priced
+    pub price: u128,         // stable per other token, Q64.64
+    pub updated_at: i64,     // last time price followed spot
+    pub bump: u8,
+}
+
+impl AmmOracle {
+    /// Moves `price` towards `spot`, by at most `ORACLE_MOVE_BPS_PER_SECOND`
+    /// for each second since the last update and `ORACLE_MAX_MOVE_BPS` in
+    /// all. A spot price pushed within one transaction barely moves it.
+    pub fn update(&mut self, spot: u128, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.updated_at).max(0) as u64;
+        let max_move_bps = elapsed
+            .saturating_mul(ORACLE_MOVE_BPS_PER_SECOND)
+            .min(ORACLE_MAX_MOVE_BPS);
+        let step = self
+            .price
+            .checked_mul(max_move_bps as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / BPS_DENOMINATOR as u128;
+
+        self.price = spot.clamp(
+            self.price.saturating_sub(step),
+            self.price.saturating_add(step),
+        );
+        self.updated_at = self.updated_at.max(now);
+        Ok(())
+    }
+
+    /// Whether `price` is within `ORACLE_MAX_MOVE_BPS` of `spot`. Past that,
+    /// spot has moved further than one update can follow, whether by a real
+    /// move the oracle has not caught up with or by a pushed reserve.
+    pub fn is_settled(&self, spot: u128) -> Result<bool> {
+        let tolerance = self
+            .price
+            .checked_mul(ORACLE_MAX_MOVE_BPS as u128)
+            .ok_or(ErrorCode::Overflow)?
+            / BPS_DENOMINATOR as u128;
+        Ok(spot.abs_diff(self.price) <= tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oracle(price: u128) -> AmmOracle {
+        AmmOracle {
+            pool_config: Pubkey::default(),
+            amm_config: Pubkey::default(),
+            price,
+            updated_at: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn follows_spot_at_a_limited_rate() {
+        let mut oracle = oracle(1_000_000);
+        oracle.update(4_000_000, 10).unwrap();
+        assert_eq!(oracle.price, 1_001_000);
+
+        // Further updates in the same second do not move it.
+        oracle.update(4_000_000, 10).unwrap();
+        assert_eq!(oracle.price, 1_001_000);
+
+        oracle.update(0, 1_000).unwrap();
+        assert_eq!(oracle.price, 990_990);
+    }
+
+    #[test]
+    fn settles_on_spot_once_within_reach() {
+        let mut oracle = oracle(1_000_000);
+        oracle.update(1_000_500, 60).unwrap();
+        assert_eq!(oracle.price, 1_000_500);
+    }
+
+    #[test]
+    fn is_settled_only_near_spot() {
+        let oracle = oracle(1_000_000);
+        assert!(oracle.is_settled(1_000_000).unwrap());
+        assert!(oracle.is_settled(1_010_000).unwrap());
+        assert!(oracle.is_settled(990_000).unwrap());
+        assert!(!oracle.is_settled(1_010_001).unwrap());
+        assert!(!oracle.is_settled(4_000_000).unwrap());
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ClaimStatus {
+    Pending,
+    Rejected,
+    Paid,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Claim {
+    pub pool_config: Pubkey,
+    pub policy: Pubkey,
+    pub lp_owner: Pubkey,
+    pub threshold: u16, // IL reported by the claimant in basis points
+    pub amount: u64,    // payout if the claim is not rejected
+    pub submitted_at: i64,
+    pub challenge_ends_at: i64, // votes close and payout opens at this time
+    pub reject_votes: u64,      // share tokens escrowed against the claim
+    pub status: ClaimStatus,
+    pub nonce: u32, // policy's claims_submitted at submission; part of the seeds
+    pub bump: u8,
+}
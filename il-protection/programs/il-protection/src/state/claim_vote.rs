@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimVote {
+    pub claim: Pubkey,
+    pub voter: Pubkey,
+    pub shares: u64, // share tokens escrowed in the claim's vote vault
+    pub bump: u8,
+}
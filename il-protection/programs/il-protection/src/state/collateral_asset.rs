@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct CollateralAsset {
+    pub pool_config: Pubkey,
+    pub mint: Pubkey,     // stablecoin accepted alongside the pool mint
+    pub vault: Pubkey,    // pool config's token account for `mint`
+    pub haircut_bps: u16, // discount applied when valuing the vault
+    pub bump: u8,
+}
@@ -1,7 +1,17 @@
+pub mod amm_oracle;
+pub mod claim;
+pub mod claim_vote;
+pub mod collateral_asset;
 pub mod policy;
 pub mod pool_config;
+pub mod reinsurance_treaty;
 pub mod underwriter_stake;
 
+pub use amm_oracle::*;
+pub use claim::*;
+pub use claim_vote::*;
+pub use collateral_asset::*;
 pub use policy::*;
 pub use pool_config::*;
+pub use reinsurance_treaty::*;
 pub use underwriter_stake::*;
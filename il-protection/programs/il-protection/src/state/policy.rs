@@ -1,14 +1,47 @@
+use crate::pricing::PremiumSchedule;
 use anchor_lang::prelude::*;
 
 #[account]
 #[derive(InitSpace)]
 pub struct Policy {
     pub pool_config: Pubkey,
+    pub lp_owner: Pubkey, // current holder; part of the policy's seeds
     pub policy_id: u64,
-    pub threshold: u16,
+    pub threshold: u16, // priced at purchase; claims must report IL above it
     pub locked_shares: u64,
     pub coverage_amount: u64,
+    pub premium: u64,        // premium paid into the premium reserve
+    pub premium_vested: u64, // premium already released to underwriters
     pub start_time: i64,
     pub expiry_time: i64,
+    pub claim_pending: bool,
+    pub claims_submitted: u32,   // nonce in the next claim's seeds
+    pub settlement_mint: Pubkey, // mint claims pay out in; pool_mint or a basket asset
+    pub amm_config: Pubkey,      // insured amm-program pool; default for plain coverage
+    pub lp_amount: u64,          // insured LP tokens
+    pub hold_x: u64,             // x redeemable for lp_amount at purchase
+    pub hold_y: u64,             // y redeemable for lp_amount at purchase
     pub bump: u8,
 }
+
+impl PremiumSchedule for Policy {
+    fn premium(&self) -> u64 {
+        self.premium
+    }
+
+    fn premium_vested(&self) -> u64 {
+        self.premium_vested
+    }
+
+    fn set_premium_vested(&mut self, premium_vested: u64) {
+        self.premium_vested = premium_vested;
+    }
+
+    fn start_time(&self) -> i64 {
+        self.start_time
+    }
+
+    fn expiry_time(&self) -> i64 {
+        self.expiry_time
+    }
+}
@@ -1,14 +1,214 @@
+use crate::{
+    error::ErrorCode,
+    pricing::{premium_accrual_rate, PremiumSchedule},
+    PREMIUM_ACCRUAL_SCALE,
+};
 use anchor_lang::prelude::*;
 
 #[account]
 #[derive(InitSpace)]
 pub struct PoolConfig {
-    pub pool_id: u64,       // unique pool identifier
-    pub premium_rate: u16,  // premium rate in basis points (10_000)
-    pub total_shares: u64,  // number of shares held by the pool
-    pub locked_shares: u64, // number of shares locked
-    pub threshold_max: u16, // maximum threshold in basis points (10_000)
-    pub pool_vault: Pubkey, // token account storing the tokens
-    pub pool_mint: Pubkey,  // stablecoin mint for pool
-    pub bump: u8,           // bump for policy account
+    pub pool_id: u64,               // unique pool identifier
+    pub admin: Pubkey,              // authority allowed to update and pause the pool
+    pub premium_rate: u16,          // premium rate in basis points (10_000)
+    pub total_shares: u64,          // number of shares held by the pool
+    pub locked_shares: u64,         // number of shares locked
+    pub claims_paid: u64,           // lifetime claim payouts, measured against reinsurance
+    pub reinsurance_recovered: u64, // lifetime recoveries received from all treaties
+    pub threshold_max: u16,         // maximum threshold in basis points (10_000)
+    pub pool_vault: Pubkey,         // token account storing the tokens
+    pub pool_mint: Pubkey,          // stablecoin mint for pool
+    pub premium_vault: Pubkey,      // unearned premiums, vested into pool_vault over each policy
+    pub share_mint: Pubkey,         // SPL token representing underwriter shares
+    pub withdrawal_cooldown: i64,   // seconds between requesting and completing a withdrawal
+    pub cancellation_fee_bps: u16,  // share of the unused premium kept on cancellation
+    pub paused: bool,               // blocks new policies and new collateral
+    pub assessor: Pubkey,           // may reject claims during the challenge window
+    pub challenge_window: i64,      // seconds a submitted claim can be challenged (0 = disabled)
+    pub auto_settle_limit: u64,     // claims up to this coverage settle instantly
+    pub collateral_assets: u8,      // basket assets registered besides pool_mint
+    pub accrual_rate: u128,         // premium earned per second by live covers (scaled)
+    pub premium_accrued: u128,      // premium earned but not yet vested into pool_vault (scaled)
+    pub premium_accrued_at: i64,    // when premium_accrued was last brought up to date
+    pub share_bump: u8,             // bump for share mint
+    pub bump: u8,                   // bump for policy account
+}
+
+impl PoolConfig {
+    /// Premium earned by live covers by `now` that is still in the premium
+    /// vault. Covers past expiry keep accruing until they are expired, so
+    /// callers cap this at the premium vault balance.
+    pub fn earned_premium(&self, now: i64) -> Result<u64> {
+        let earned = self
+            .premium_accrued
+            .checked_add(self.accrual_since(now)?)
+            .ok_or(ErrorCode::Overflow)?;
+        u64::try_from(earned / PREMIUM_ACCRUAL_SCALE).map_err(|_| ErrorCode::Overflow.into())
+    }
+
+    /// Starts accruing `cover`'s premium.
+    pub fn add_cover(&mut self, cover: &impl PremiumSchedule, now: i64) -> Result<()> {
+        self.accrue_premium(now)?;
+        self.accrual_rate = self
+            .accrual_rate
+            .checked_add(premium_accrual_rate(cover))
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Records `amount` of premium vested into the pool vault.
+    pub fn release_premium(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.accrue_premium(now)?;
+        self.premium_accrued = self
+            .premium_accrued
+            .saturating_sub(amount as u128 * PREMIUM_ACCRUAL_SCALE);
+        Ok(())
+    }
+
+    /// Stops accruing `cover`'s premium and drops what it accrued beyond its
+    /// `premium_vested`.
+    pub fn remove_cover(&mut self, cover: &impl PremiumSchedule, now: i64) -> Result<()> {
+        self.accrue_premium(now)?;
+        let rate = premium_accrual_rate(cover);
+        self.accrual_rate = self.accrual_rate.saturating_sub(rate);
+
+        let elapsed = now.saturating_sub(cover.start_time()).max(0) as u128;
+        let accrued = rate.checked_mul(elapsed).ok_or(ErrorCode::Overflow)?;
+        let released = cover.premium_vested() as u128 * PREMIUM_ACCRUAL_SCALE;
+        self.premium_accrued = self
+            .premium_accrued
+            .checked_add(released)
+            .ok_or(ErrorCode::Overflow)?
+            .saturating_sub(accrued);
+        Ok(())
+    }
+
+    fn accrue_premium(&mut self, now: i64) -> Result<()> {
+        self.premium_accrued = self
+            .premium_accrued
+            .checked_add(self.accrual_since(now)?)
+            .ok_or(ErrorCode::Overflow)?;
+        self.premium_accrued_at = self.premium_accrued_at.max(now);
+        Ok(())
+    }
+
+    fn accrual_since(&self, now: i64) -> Result<u128> {
+        let elapsed = now.saturating_sub(self.premium_accrued_at).max(0) as u128;
+        self.accrual_rate
+            .checked_mul(elapsed)
+            .ok_or(ErrorCode::Overflow.into())
+    }
+}
+
+/// An idle pool with the flat 5% rate, shared by the unit tests.
+#[cfg(test)]
+pub(crate) fn test_pool() -> PoolConfig {
+    PoolConfig {
+        pool_id: 1,
+        admin: Pubkey::default(),
+        premium_rate: 500,
+        total_shares: 0,
+        locked_shares: 0,
+        claims_paid: 0,
+        reinsurance_recovered: 0,
+        threshold_max: 2_000,
+        pool_vault: Pubkey::default(),
+        pool_mint: Pubkey::default(),
+        premium_vault: Pubkey::default(),
+        share_mint: Pubkey::default(),
+        withdrawal_cooldown: 0,
+        cancellation_fee_bps: 0,
+        paused: false,
+        assessor: Pubkey::default(),
+        challenge_window: 0,
+        auto_settle_limit: 0,
+        collateral_assets: 0,
+        accrual_rate: 0,
+        premium_accrued: 0,
+        premium_accrued_at: 0,
+        share_bump: 255,
+        bump: 255,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Cover {
+        premium: u64,
+        premium_vested: u64,
+        start_time: i64,
+        expiry_time: i64,
+    }
+
+    impl PremiumSchedule for Cover {
+        fn premium(&self) -> u64 {
+            self.premium
+        }
+
+        fn premium_vested(&self) -> u64 {
+            self.premium_vested
+        }
+
+        fn set_premium_vested(&mut self, premium_vested: u64) {
+            self.premium_vested = premium_vested;
+        }
+
+        fn start_time(&self) -> i64 {
+            self.start_time
+        }
+
+        fn expiry_time(&self) -> i64 {
+            self.expiry_time
+        }
+    }
+
+    fn cover(premium: u64, start_time: i64, expiry_time: i64) -> Cover {
+        Cover {
+            premium,
+            premium_vested: 0,
+            start_time,
+            expiry_time,
+        }
+    }
+
+    #[test]
+    fn accrues_premium_until_it_is_released() {
+        let mut pool = test_pool();
+        pool.add_cover(&cover(1_000, 0, 100), 0).unwrap();
+        assert_eq!(pool.earned_premium(50).unwrap(), 500);
+
+        pool.release_premium(500, 50).unwrap();
+        assert_eq!(pool.earned_premium(50).unwrap(), 0);
+        assert_eq!(pool.earned_premium(75).unwrap(), 250);
+    }
+
+    #[test]
+    fn removed_covers_stop_accruing() {
+        let mut pool = test_pool();
+        let short = cover(1_000, 0, 100);
+        pool.add_cover(&short, 0).unwrap();
+        pool.add_cover(&cover(1_000, 0, 200), 0).unwrap();
+        // The expired cover keeps accruing until it is released.
+        assert_eq!(pool.earned_premium(150).unwrap(), 2_250);
+
+        pool.remove_cover(&short, 150).unwrap();
+        assert_eq!(pool.earned_premium(150).unwrap(), 750);
+        assert_eq!(pool.earned_premium(200).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn early_release_keeps_other_covers_accrued() {
+        let mut pool = test_pool();
+        let mut claimed = cover(1_000, 0, 100);
+        pool.add_cover(&claimed, 0).unwrap();
+        pool.release_premium(200, 20).unwrap();
+        claimed.set_premium_vested(200);
+        pool.add_cover(&cover(500, 20, 120), 20).unwrap();
+
+        // A claim releases the rest of the premium ahead of schedule.
+        pool.remove_cover(&claimed, 40).unwrap();
+        assert_eq!(pool.earned_premium(40).unwrap(), 100);
+    }
 }
@@ -0,0 +1,130 @@
+use crate::pricing::PremiumSchedule;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReinsuranceTreaty {
+    pub primary_pool: Pubkey,      // pool whose losses are covered
+    pub reinsurer_pool: Pubkey,    // pool whose vault pays recoveries
+    pub attachment: u64,           // primary losses retained before the treaty pays
+    pub limit: u64,                // most the treaty pays over its term
+    pub claims_paid_at_start: u64, // primary claims_paid when the treaty was bought
+    pub recovered_at_start: u64,   // primary reinsurance_recovered when the treaty was bought
+    pub recovered: u64,            // paid to the primary pool so far
+    pub locked_shares: u64,        // reinsurer shares backing the limit
+    pub premium: u64,              // premium paid into the reinsurer's premium vault
+    pub premium_vested: u64,       // premium already released to the reinsurer
+    pub start_time: i64,
+    pub expiry_time: i64,
+    pub bump: u8,
+}
+
+impl ReinsuranceTreaty {
+    /// Amount still owed to the primary pool given its lifetime `claims_paid`
+    /// and `reinsurance_recovered`: losses since the treaty started, net of
+    /// what other treaties recovered in that time, above `attachment`, up to
+    /// `limit`. Treaties on the same pool therefore never pay a loss twice.
+    pub fn recoverable(&self, claims_paid: u64, reinsurance_recovered: u64) -> u64 {
+        let losses = claims_paid.saturating_sub(self.claims_paid_at_start);
+        let recovered_elsewhere = reinsurance_recovered
+            .saturating_sub(self.recovered_at_start)
+            .saturating_sub(self.recovered);
+        let retained = losses.saturating_sub(recovered_elsewhere);
+        let covered = retained.saturating_sub(self.attachment).min(self.limit);
+        covered.saturating_sub(self.recovered)
+    }
+}
+
+impl PremiumSchedule for ReinsuranceTreaty {
+    fn premium(&self) -> u64 {
+        self.premium
+    }
+
+    fn premium_vested(&self) -> u64 {
+        self.premium_vested
+    }
+
+    fn set_premium_vested(&mut self, premium_vested: u64) {
+        self.premium_vested = premium_vested;
+    }
+
+    fn start_time(&self) -> i64 {
+        self.start_time
+    }
+
+    fn expiry_time(&self) -> i64 {
+        self.expiry_time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn treaty(attachment: u64, limit: u64, recovered: u64) -> ReinsuranceTreaty {
+        ReinsuranceTreaty {
+            primary_pool: Pubkey::default(),
+            reinsurer_pool: Pubkey::default(),
+            attachment,
+            limit,
+            claims_paid_at_start: 1_000,
+            recovered_at_start: 0,
+            recovered,
+            locked_shares: 0,
+            premium: 0,
+            premium_vested: 0,
+            start_time: 0,
+            expiry_time: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn pays_losses_between_attachment_and_limit() {
+        let layer = treaty(500, 2_000, 0);
+        assert_eq!(layer.recoverable(1_000, 0), 0);
+        assert_eq!(layer.recoverable(1_500, 0), 0);
+        assert_eq!(layer.recoverable(1_800, 0), 300);
+        assert_eq!(layer.recoverable(3_500, 0), 2_000);
+        assert_eq!(layer.recoverable(9_000, 0), 2_000);
+    }
+
+    #[test]
+    fn subtracts_earlier_recoveries() {
+        let layer = treaty(500, 2_000, 300);
+        assert_eq!(layer.recoverable(1_800, 300), 0);
+        assert_eq!(layer.recoverable(2_000, 300), 200);
+        assert_eq!(layer.recoverable(9_000, 300), 1_700);
+    }
+
+    #[test]
+    fn treaties_on_one_pool_do_not_pay_the_same_loss() {
+        let mut first = treaty(500, 2_000, 0);
+        let mut second = treaty(500, 2_000, 0);
+
+        // 2_500 of losses: the first layer to recover takes 2_000 of it.
+        let owed = first.recoverable(3_500, 0);
+        assert_eq!(owed, 2_000);
+        first.recovered += owed;
+        let mut pool_recovered = owed;
+
+        // The pool has only retained 500, which sits under the second attachment.
+        assert_eq!(second.recoverable(3_500, pool_recovered), 0);
+        assert_eq!(first.recoverable(3_500, pool_recovered), 0);
+
+        // Further losses reach the second layer once the retention passes 500.
+        let owed = second.recoverable(4_200, pool_recovered);
+        assert_eq!(owed, 700);
+        second.recovered += owed;
+        pool_recovered += owed;
+        assert_eq!(first.recoverable(4_200, pool_recovered), 0);
+        assert_eq!(second.recoverable(4_200, pool_recovered), 0);
+    }
+
+    #[test]
+    fn ignores_recoveries_before_the_treaty_started() {
+        let mut layer = treaty(500, 2_000, 0);
+        layer.recovered_at_start = 5_000;
+        assert_eq!(layer.recoverable(1_800, 5_000), 300);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::{error::ErrorCode, share_math};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -5,6 +6,93 @@ use anchor_lang::prelude::*;
 pub struct UnderwriterStake {
     pub underwriter: Pubkey,
     pub pool_config: Pubkey,
-    pub shares: u64, // shares of underwriter for the pool
+    pub pending_shares: u64, // shares queued for withdrawal, held in the withdrawal escrow
+    pub withdrawal_requested_at: i64, // when the pending withdrawal was queued
+    pub entry_share_price: u64, // average price paid per share, scaled by SHARE_PRICE_SCALE
+    pub basis_shares: u64,   // shares bought by staking that entry_share_price applies to
+    pub realized_pnl: i64,   // gains minus losses on completed withdrawals
     pub bump: u8,
 }
+
+impl UnderwriterStake {
+    /// Shares out of `held` with a recorded entry price. Shares that have
+    /// been transferred away take their basis with them, and shares received
+    /// by transfer have none.
+    pub fn basis_held(&self, held: u64) -> u64 {
+        self.basis_shares.min(held)
+    }
+
+    /// Records `new_shares` bought for `cost` by a holder who held `held`
+    /// shares beforehand.
+    pub fn add_basis(&mut self, held: u64, new_shares: u64, cost: u64) -> Result<()> {
+        let basis_shares = self.basis_held(held);
+        self.entry_share_price = share_math::average_entry_price(
+            basis_shares,
+            self.entry_share_price,
+            new_shares,
+            cost,
+        )?;
+        self.basis_shares = basis_shares
+            .checked_add(new_shares)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Removes `shares` out of `held` and returns how many of them had a
+    /// recorded entry price. Shares with a basis are counted first.
+    pub fn remove_basis(&mut self, held: u64, shares: u64) -> Result<u64> {
+        let remaining = held.checked_sub(shares).ok_or(ErrorCode::Overflow)?;
+        let basis_shares = self.basis_held(held);
+        let tracked = shares.min(basis_shares);
+        self.basis_shares = (basis_shares - tracked).min(remaining);
+        Ok(tracked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SHARE_PRICE_SCALE;
+
+    fn stake() -> UnderwriterStake {
+        UnderwriterStake {
+            underwriter: Pubkey::default(),
+            pool_config: Pubkey::default(),
+            pending_shares: 0,
+            withdrawal_requested_at: 0,
+            entry_share_price: 0,
+            basis_shares: 0,
+            realized_pnl: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn transferred_shares_carry_no_basis() {
+        let mut stake = stake();
+        stake.add_basis(0, 100, 100).unwrap();
+        assert_eq!(stake.entry_share_price, SHARE_PRICE_SCALE);
+
+        // 50 shares received by transfer do not dilute the entry price.
+        stake.add_basis(150, 100, 200).unwrap();
+        assert_eq!(stake.basis_shares, 200);
+        assert_eq!(stake.entry_share_price, 3 * SHARE_PRICE_SCALE / 2);
+        assert_eq!(stake.basis_held(250), 200);
+
+        // Withdrawing 220 of 250 finds a basis for 200 of them.
+        assert_eq!(stake.remove_basis(250, 220).unwrap(), 200);
+        assert_eq!(stake.basis_held(30), 0);
+    }
+
+    #[test]
+    fn shares_sent_away_drop_their_basis() {
+        let mut stake = stake();
+        stake.add_basis(0, 100, 100).unwrap();
+
+        // 60 shares were transferred out before the next stake.
+        stake.add_basis(40, 10, 20).unwrap();
+        assert_eq!(stake.basis_shares, 50);
+        assert_eq!(stake.remove_basis(50, 30).unwrap(), 30);
+        assert_eq!(stake.basis_shares, 20);
+    }
+}
@@ -0,0 +1,763 @@
+//! Scenario tests for pool accounting, run in an in-process bank against the
+//! program built by `anchor build`. Anchor's CPI helpers only run on the SBF
+//! target, so a plain `cargo test` skips the suite. `anchor test` and CI
+//! (`.github/workflows/il-protection.yml`) build the program first and run it:
+//!
+//! ```sh
+//! anchor build && cargo test -p il-protection --test invariants -- --ignored
+//! ```
+//!
+//! After every step they check that
+//! - share tokens held by underwriters (including those escrowed as claim
+//!   votes or queued withdrawals) add up to the share mint supply and
+//!   `PoolConfig::total_shares`,
+//! - each withdrawal escrow holds exactly the stake's `pending_shares` and
+//!   each vote escrow its vote's `shares`,
+//! - `locked_shares` equals the sum over open policies and never exceeds
+//!   `total_shares`,
+//! - the pool vault covers the coverage of every open policy.
+
+use anchor_lang::{
+    prelude::{Clock, Pubkey},
+    solana_program::{
+        instruction::Instruction, program_pack::Pack, system_instruction, system_program,
+    },
+    AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address_with_program_id,
+        spl_associated_token_account::instruction::create_associated_token_account,
+    },
+    token_2022::spl_token_2022::{
+        self,
+        extension::StateWithExtensions,
+        state::{Account as TokenAccount, Mint},
+    },
+};
+use il_protection::{
+    Claim, ClaimStatus, ClaimVote, Policy, PoolConfig, UnderwriterStake, WITHDRAWAL_WINDOW,
+};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    bpf_loader,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+const POOL_ID: u64 = 1;
+const DECIMALS: u8 = 6;
+const TOKEN: u64 = 1_000_000;
+const DAY: i64 = 86_400;
+const MONTH: i64 = 2_592_000;
+const PROGRAM_SO: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../target/deploy/il_protection.so"
+);
+
+struct Pool {
+    ctx: ProgramTestContext,
+    mint: Pubkey,
+    pool_config: Pubkey,
+    pool_vault: Pubkey,
+    premium_vault: Pubkey,
+    share_mint: Pubkey,
+    underwriters: Vec<Pubkey>,
+    policies: Vec<Pubkey>,
+    votes: Vec<Pubkey>,
+}
+
+impl Pool {
+    async fn new() -> Self {
+        let mut program_test = ProgramTest::default();
+        let elf = std::fs::read(PROGRAM_SO).expect("run `anchor build` first");
+        program_test.add_account(
+            il_protection::ID,
+            Account {
+                lamports: 1_000_000_000,
+                data: elf,
+                owner: bpf_loader::ID,
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+        let mut ctx = program_test.start_with_context().await;
+
+        let mint = Keypair::new();
+        let payer = ctx.payer.pubkey();
+        let rent = ctx.banks_client.get_rent().await.unwrap();
+        let ixs = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(Mint::LEN),
+                Mint::LEN as u64,
+                &spl_token_2022::ID,
+            ),
+            spl_token_2022::instruction::initialize_mint2(
+                &spl_token_2022::ID,
+                &mint.pubkey(),
+                &payer,
+                None,
+                DECIMALS,
+            )
+            .unwrap(),
+        ];
+        send(&mut ctx, &ixs, &[&mint]).await.unwrap();
+
+        let pool_config = pda(&[b"pool_config", &POOL_ID.to_le_bytes()]);
+        let mut pool = Self {
+            mint: mint.pubkey(),
+            pool_config,
+            pool_vault: ata(&pool_config, &mint.pubkey()),
+            premium_vault: pda(&[b"premium_vault", pool_config.as_ref()]),
+            share_mint: pda(&[b"share_mint", pool_config.as_ref()]),
+            underwriters: vec![],
+            policies: vec![],
+            votes: vec![],
+            ctx,
+        };
+
+        let ix = instruction(
+            il_protection::accounts::InitializePool {
+                pool_config: pool.pool_config,
+                pool_vault: pool.pool_vault,
+                mint: pool.mint,
+                premium_vault: pool.premium_vault,
+                share_mint: pool.share_mint,
+                signer: payer,
+                token_program: spl_token_2022::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            il_protection::instruction::InitializePool {
+                pool_id: POOL_ID,
+                premium_rate: 500,
+                threshold_max: 2_000,
+                withdrawal_cooldown: 0,
+                cancellation_fee_bps: 1_000,
+            },
+        );
+        pool.send(&[ix], &[]).await.unwrap();
+        pool
+    }
+
+    async fn send(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        send(&mut self.ctx, ixs, signers).await
+    }
+
+    /// A funded wallet with a pool-mint token account holding `amount`.
+    async fn wallet(&mut self, amount: u64) -> Keypair {
+        let wallet = Keypair::new();
+        let payer = self.ctx.payer.pubkey();
+        let token_account = ata(&wallet.pubkey(), &self.mint);
+        let ixs = [
+            system_instruction::transfer(&payer, &wallet.pubkey(), 1_000_000_000),
+            create_associated_token_account(
+                &payer,
+                &wallet.pubkey(),
+                &self.mint,
+                &spl_token_2022::ID,
+            ),
+            spl_token_2022::instruction::mint_to(
+                &spl_token_2022::ID,
+                &self.mint,
+                &token_account,
+                &payer,
+                &[],
+                amount,
+            )
+            .unwrap(),
+        ];
+        self.send(&ixs, &[]).await.unwrap();
+        wallet
+    }
+
+    async fn stake(&mut self, underwriter: &Keypair, amount: u64) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            il_protection::accounts::StakeCollateral {
+                payer: self.ctx.payer.pubkey(),
+                pool_config: self.pool_config,
+                underwriter_stake: stake_pda(&underwriter.pubkey()),
+                pool_vault: self.pool_vault,
+                premium_vault: self.premium_vault,
+                pool_mint: self.mint,
+                share_mint: self.share_mint,
+                underwriter: underwriter.pubkey(),
+                underwriter_ata: ata(&underwriter.pubkey(), &self.mint),
+                underwriter_share_ata: ata(&underwriter.pubkey(), &self.share_mint),
+                token_program: spl_token_2022::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            il_protection::instruction::StakeCollateral { amount },
+        );
+        self.send(&[ix], &[underwriter]).await?;
+        if !self.underwriters.contains(&underwriter.pubkey()) {
+            self.underwriters.push(underwriter.pubkey());
+        }
+        Ok(())
+    }
+
+    async fn buy(
+        &mut self,
+        lp_owner: &Keypair,
+        coverage_amount: u64,
+        duration: i64,
+    ) -> Result<(), BanksClientError> {
+        let policy = policy_pda(&self.pool_config, &lp_owner.pubkey());
+        let ix = instruction(
+            il_protection::accounts::BuyProtection {
+                lp_owner: lp_owner.pubkey(),
+                payer: self.ctx.payer.pubkey(),
+                pool_config: self.pool_config,
+                policy,
+                pool_vault: self.pool_vault,
+                premium_vault: self.premium_vault,
+                pool_mint: self.mint,
+                lp_owner_ata: ata(&lp_owner.pubkey(), &self.mint),
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            il_protection::instruction::BuyProtection {
+                threshold: 1_000,
+                coverage_amount,
+                duration,
+            },
+        );
+        self.send(&[ix], &[lp_owner]).await?;
+        self.policies.push(policy);
+        Ok(())
+    }
+
+    async fn claim(&mut self, lp_owner: &Keypair) -> Result<(), BanksClientError> {
+        let policy = policy_pda(&self.pool_config, &lp_owner.pubkey());
+        let ix = instruction(
+            il_protection::accounts::ClaimProtection {
+                payer: self.ctx.payer.pubkey(),
+                lp_owner: lp_owner.pubkey(),
+                pool_config: self.pool_config,
+                policy,
+                pool_vault: self.pool_vault,
+                premium_vault: self.premium_vault,
+                pool_mint: self.mint,
+                lp_owner_ata: ata(&lp_owner.pubkey(), &self.mint),
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            il_protection::instruction::ClaimProtection { threshold: 2_500 },
+        );
+        self.send(&[ix], &[lp_owner]).await?;
+        self.policies.retain(|p| *p != policy);
+        Ok(())
+    }
+
+    async fn expire(&mut self, lp_owner: &Pubkey) -> Result<(), BanksClientError> {
+        let policy = policy_pda(&self.pool_config, lp_owner);
+        let ix = instruction(
+            il_protection::accounts::ExpirePolicy {
+                lp_owner: *lp_owner,
+                pool_config: self.pool_config,
+                policy,
+                pool_vault: self.pool_vault,
+                premium_vault: self.premium_vault,
+                pool_mint: self.mint,
+                token_program: spl_token_2022::ID,
+            },
+            il_protection::instruction::ExpirePolicy {},
+        );
+        self.send(&[ix], &[]).await?;
+        self.policies.retain(|p| *p != policy);
+        Ok(())
+    }
+
+    async fn release(&mut self, lp_owner: &Pubkey) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            il_protection::accounts::ReleasePremium {
+                pool_config: self.pool_config,
+                policy: policy_pda(&self.pool_config, lp_owner),
+                pool_vault: self.pool_vault,
+                premium_vault: self.premium_vault,
+                pool_mint: self.mint,
+                token_program: spl_token_2022::ID,
+            },
+            il_protection::instruction::ReleasePremium {},
+        );
+        self.send(&[ix], &[]).await
+    }
+
+    async fn withdraw(
+        &mut self,
+        underwriter: &Keypair,
+        shares: u64,
+    ) -> Result<(), BanksClientError> {
+        let ixs = [
+            self.request_ix(underwriter, shares),
+            self.complete_ix(underwriter),
+        ];
+        self.send(&ixs, &[underwriter]).await
+    }
+
+    fn request_ix(&self, underwriter: &Keypair, shares: u64) -> Instruction {
+        instruction(
+            il_protection::accounts::RequestWithdrawal {
+                underwriter: underwriter.pubkey(),
+                pool_config: self.pool_config,
+                underwriter_stake: stake_pda(&underwriter.pubkey()),
+                share_mint: self.share_mint,
+                underwriter_share_ata: ata(&underwriter.pubkey(), &self.share_mint),
+                withdrawal_escrow: self.escrow(&underwriter.pubkey()),
+                token_program: spl_token_2022::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            il_protection::instruction::RequestWithdrawal { shares },
+        )
+    }
+
+    fn cancel_ix(&self, underwriter: &Keypair) -> Instruction {
+        instruction(
+            il_protection::accounts::CancelWithdrawal {
+                underwriter: underwriter.pubkey(),
+                pool_config: self.pool_config,
+                underwriter_stake: stake_pda(&underwriter.pubkey()),
+                share_mint: self.share_mint,
+                underwriter_share_ata: ata(&underwriter.pubkey(), &self.share_mint),
+                withdrawal_escrow: self.escrow(&underwriter.pubkey()),
+                token_program: spl_token_2022::ID,
+            },
+            il_protection::instruction::CancelWithdrawal {},
+        )
+    }
+
+    fn complete_ix(&self, underwriter: &Keypair) -> Instruction {
+        instruction(
+            il_protection::accounts::CompleteWithdrawal {
+                pool_config: self.pool_config,
+                underwriter_stake: stake_pda(&underwriter.pubkey()),
+                pool_vault: self.pool_vault,
+                pool_mint: self.mint,
+                share_mint: self.share_mint,
+                underwriter: underwriter.pubkey(),
+                payer: self.ctx.payer.pubkey(),
+                underwriter_ata: ata(&underwriter.pubkey(), &self.mint),
+                underwriter_share_ata: ata(&underwriter.pubkey(), &self.share_mint),
+                withdrawal_escrow: self.escrow(&underwriter.pubkey()),
+                token_program: spl_token_2022::ID,
+                system_program: system_program::ID,
+            },
+            il_protection::instruction::CompleteWithdrawal {},
+        )
+    }
+
+    /// Sends claims above the auto-settle limit (none) to review for
+    /// `challenge_window` seconds, with the payer as assessor.
+    async fn enable_review(&mut self, challenge_window: i64) -> Result<(), BanksClientError> {
+        let admin = self.ctx.payer.pubkey();
+        let ix = instruction(
+            il_protection::accounts::UpdatePool {
+                admin,
+                pool_config: self.pool_config,
+            },
+            il_protection::instruction::UpdateClaimsConfig {
+                assessor: admin,
+                challenge_window,
+                auto_settle_limit: 0,
+            },
+        );
+        self.send(&[ix], &[]).await
+    }
+
+    /// Opens a claim on `lp_owner`'s policy and returns its address.
+    async fn submit(&mut self, lp_owner: &Keypair) -> Result<Pubkey, BanksClientError> {
+        let policy = policy_pda(&self.pool_config, &lp_owner.pubkey());
+        let nonce = self.account::<Policy>(policy).await.claims_submitted;
+        let claim = pda(&[b"claim", policy.as_ref(), &nonce.to_le_bytes()]);
+        let ix = instruction(
+            il_protection::accounts::SubmitClaim {
+                payer: self.ctx.payer.pubkey(),
+                lp_owner: lp_owner.pubkey(),
+                pool_config: self.pool_config,
+                policy,
+                claim,
+                system_program: system_program::ID,
+            },
+            il_protection::instruction::SubmitClaim { threshold: 2_500 },
+        );
+        self.send(&[ix], &[lp_owner]).await?;
+        Ok(claim)
+    }
+
+    async fn vote(
+        &mut self,
+        voter: &Keypair,
+        claim: Pubkey,
+        shares: u64,
+    ) -> Result<(), BanksClientError> {
+        let claim_vote = vote_pda(&claim, &voter.pubkey());
+        let policy = self.account::<Claim>(claim).await.policy;
+        let ix = instruction(
+            il_protection::accounts::VoteRejectClaim {
+                voter: voter.pubkey(),
+                pool_config: self.pool_config,
+                policy,
+                claim,
+                claim_vote,
+                share_mint: self.share_mint,
+                voter_share_ata: ata(&voter.pubkey(), &self.share_mint),
+                vote_escrow: ata(&claim_vote, &self.share_mint),
+                token_program: spl_token_2022::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            il_protection::instruction::VoteRejectClaim { shares },
+        );
+        self.send(&[ix], &[voter]).await?;
+        if !self.votes.contains(&claim_vote) {
+            self.votes.push(claim_vote);
+        }
+        Ok(())
+    }
+
+    async fn withdraw_vote(
+        &mut self,
+        voter: &Keypair,
+        claim: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let claim_vote = vote_pda(&claim, &voter.pubkey());
+        let ix = instruction(
+            il_protection::accounts::WithdrawVote {
+                voter: voter.pubkey(),
+                pool_config: self.pool_config,
+                claim,
+                claim_vote,
+                share_mint: self.share_mint,
+                voter_share_ata: ata(&voter.pubkey(), &self.share_mint),
+                vote_escrow: ata(&claim_vote, &self.share_mint),
+                token_program: spl_token_2022::ID,
+            },
+            il_protection::instruction::WithdrawVote {},
+        );
+        self.send(&[ix], &[voter]).await?;
+        self.votes.retain(|v| *v != claim_vote);
+        Ok(())
+    }
+
+    async fn warp(&mut self, seconds: i64) {
+        let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    async fn pool_config(&mut self) -> PoolConfig {
+        self.account(self.pool_config).await
+    }
+
+    async fn shares_of(&mut self, underwriter: &Pubkey) -> u64 {
+        self.token_balance(ata(underwriter, &self.share_mint)).await
+    }
+
+    /// Share account holding `underwriter`'s queued withdrawal.
+    fn escrow(&self, underwriter: &Pubkey) -> Pubkey {
+        ata(&stake_pda(underwriter), &self.share_mint)
+    }
+
+    async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Zero for token accounts that do not exist (yet or any more).
+    async fn token_balance(&mut self, address: Pubkey) -> u64 {
+        let Some(account) = self.ctx.banks_client.get_account(address).await.unwrap() else {
+            return 0;
+        };
+        StateWithExtensions::<TokenAccount>::unpack(&account.data)
+            .unwrap()
+            .base
+            .amount
+    }
+
+    async fn assert_invariants(&mut self) {
+        let pool = self.pool_config().await;
+
+        let mint = self
+            .ctx
+            .banks_client
+            .get_account(self.share_mint)
+            .await
+            .unwrap()
+            .unwrap();
+        let supply = StateWithExtensions::<Mint>::unpack(&mint.data)
+            .unwrap()
+            .base
+            .supply;
+        let mut held = 0;
+        for underwriter in self.underwriters.clone() {
+            held += self.shares_of(&underwriter).await;
+            let escrowed = self.token_balance(self.escrow(&underwriter)).await;
+            held += escrowed;
+            let stake: UnderwriterStake = self.account(stake_pda(&underwriter)).await;
+            assert_eq!(stake.pending_shares, escrowed, "pending != escrowed");
+        }
+        for claim_vote in self.votes.clone() {
+            let escrowed = self.token_balance(ata(&claim_vote, &self.share_mint)).await;
+            held += escrowed;
+            let vote: ClaimVote = self.account(claim_vote).await;
+            assert_eq!(vote.shares, escrowed, "vote shares != escrowed");
+        }
+        assert_eq!(supply, pool.total_shares, "share supply != total_shares");
+        assert_eq!(
+            held, pool.total_shares,
+            "underwriter shares != total_shares"
+        );
+
+        let mut locked = 0;
+        let mut coverage = 0;
+        for policy in self.policies.clone() {
+            let policy: Policy = self.account(policy).await;
+            locked += policy.locked_shares;
+            coverage += policy.coverage_amount;
+        }
+        assert_eq!(locked, pool.locked_shares, "policy shares != locked_shares");
+        assert!(
+            pool.locked_shares <= pool.total_shares,
+            "locked more than total"
+        );
+
+        let vault = self.token_balance(self.pool_vault).await;
+        assert!(vault >= coverage, "vault {vault} below coverage {coverage}");
+    }
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &il_protection::ID).0
+}
+
+fn stake_pda(underwriter: &Pubkey) -> Pubkey {
+    pda(&[b"underwriter", &POOL_ID.to_le_bytes(), underwriter.as_ref()])
+}
+
+fn vote_pda(claim: &Pubkey, voter: &Pubkey) -> Pubkey {
+    pda(&[b"claim_vote", claim.as_ref(), voter.as_ref()])
+}
+
+fn policy_pda(pool_config: &Pubkey, lp_owner: &Pubkey) -> Pubkey {
+    pda(&[b"policy", pool_config.as_ref(), lp_owner.as_ref()])
+}
+
+fn ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &spl_token_2022::ID)
+}
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: il_protection::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+async fn send(
+    ctx: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&ctx.payer];
+    all_signers.extend_from_slice(signers);
+    let tx =
+        Transaction::new_signed_with_payer(ixs, Some(&ctx.payer.pubkey()), &all_signers, blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/il_protection.so; run by anchor test and CI"]
+async fn multi_underwriter_staking_tracks_total_shares() {
+    let mut pool = Pool::new().await;
+    let alice = pool.wallet(1_000 * TOKEN).await;
+    let bob = pool.wallet(1_000 * TOKEN).await;
+    let carol = pool.wallet(1_000 * TOKEN).await;
+
+    pool.stake(&alice, 100 * TOKEN).await.unwrap();
+    pool.assert_invariants().await;
+    pool.stake(&bob, 250 * TOKEN).await.unwrap();
+    pool.assert_invariants().await;
+    pool.stake(&alice, 50 * TOKEN).await.unwrap();
+    pool.assert_invariants().await;
+
+    // Premiums vest into the vault, so later stakers get fewer shares per token.
+    let lp = pool.wallet(100 * TOKEN).await;
+    pool.buy(&lp, 40 * TOKEN, MONTH).await.unwrap();
+    pool.warp(MONTH / 2).await;
+    pool.release(&lp.pubkey()).await.unwrap();
+    pool.assert_invariants().await;
+    pool.stake(&carol, 100 * TOKEN).await.unwrap();
+    pool.assert_invariants().await;
+    assert!(pool.shares_of(&carol.pubkey()).await < 100 * TOKEN);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/il_protection.so; run by anchor test and CI"]
+async fn withdrawals_respect_locked_shares() {
+    let mut pool = Pool::new().await;
+    let alice = pool.wallet(1_000 * TOKEN).await;
+    let bob = pool.wallet(1_000 * TOKEN).await;
+    pool.stake(&alice, 100 * TOKEN).await.unwrap();
+    pool.stake(&bob, 100 * TOKEN).await.unwrap();
+
+    // Lock 75% of the pool.
+    let lp = pool.wallet(200 * TOKEN).await;
+    pool.buy(&lp, 150 * TOKEN, MONTH).await.unwrap();
+    pool.assert_invariants().await;
+
+    // Each underwriter can only exit their unlocked 25%.
+    let alice_shares = pool.shares_of(&alice.pubkey()).await;
+    assert!(pool.withdraw(&alice, alice_shares).await.is_err());
+    pool.withdraw(&alice, alice_shares / 4).await.unwrap();
+    pool.assert_invariants().await;
+
+    // Coverage beyond the remaining capacity is refused.
+    let greedy = pool.wallet(200 * TOKEN).await;
+    assert!(pool.buy(&greedy, 100 * TOKEN, MONTH).await.is_err());
+    pool.assert_invariants().await;
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/il_protection.so; run by anchor test and CI"]
+async fn withdrawal_requests_lapse() {
+    let mut pool = Pool::new().await;
+    let alice = pool.wallet(1_000 * TOKEN).await;
+    pool.stake(&alice, 100 * TOKEN).await.unwrap();
+
+    let shares = pool.shares_of(&alice.pubkey()).await;
+    let request = pool.request_ix(&alice, 10 * TOKEN);
+    pool.send(&[request], &[&alice]).await.unwrap();
+    // Queued shares leave the wallet, so they cannot back another request.
+    assert_eq!(pool.shares_of(&alice.pubkey()).await, shares - 10 * TOKEN);
+    pool.assert_invariants().await;
+
+    pool.warp(WITHDRAWAL_WINDOW).await;
+    let complete = pool.complete_ix(&alice);
+    assert!(pool.send(&[complete], &[&alice]).await.is_err());
+
+    // A lapsed request can be cancelled to get the shares back.
+    let cancel = pool.cancel_ix(&alice);
+    pool.send(&[cancel], &[&alice]).await.unwrap();
+    assert_eq!(pool.shares_of(&alice.pubkey()).await, shares);
+    pool.assert_invariants().await;
+
+    // A fresh request opens a new window.
+    pool.withdraw(&alice, 10 * TOKEN).await.unwrap();
+    pool.assert_invariants().await;
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/il_protection.so; run by anchor test and CI"]
+async fn claims_and_expiry_release_locked_shares() {
+    let mut pool = Pool::new().await;
+    let alice = pool.wallet(1_000 * TOKEN).await;
+    let bob = pool.wallet(1_000 * TOKEN).await;
+    pool.stake(&alice, 200 * TOKEN).await.unwrap();
+    pool.stake(&bob, 300 * TOKEN).await.unwrap();
+
+    let lp1 = pool.wallet(100 * TOKEN).await;
+    let lp2 = pool.wallet(100 * TOKEN).await;
+    let lp3 = pool.wallet(100 * TOKEN).await;
+    pool.buy(&lp1, 100 * TOKEN, MONTH).await.unwrap();
+    pool.buy(&lp2, 150 * TOKEN, MONTH).await.unwrap();
+    pool.buy(&lp3, 50 * TOKEN, 2 * MONTH).await.unwrap();
+    pool.assert_invariants().await;
+
+    pool.claim(&lp1).await.unwrap();
+    pool.assert_invariants().await;
+    let after_claim = pool.pool_config().await;
+    assert_eq!(after_claim.claims_paid, 100 * TOKEN);
+
+    // A withdrawal after the loss is priced below the original stake.
+    let bob_shares = pool.shares_of(&bob.pubkey()).await;
+    let bob_ata = ata(&bob.pubkey(), &pool.mint);
+    let before = pool.token_balance(bob_ata).await;
+    pool.withdraw(&bob, bob_shares / 10).await.unwrap();
+    let received = pool.token_balance(bob_ata).await - before;
+    assert!(received < 30 * TOKEN);
+    let stake: UnderwriterStake = pool.account(stake_pda(&bob.pubkey())).await;
+    assert_eq!(stake.realized_pnl, received as i64 - 30 * TOKEN as i64);
+    pool.assert_invariants().await;
+
+    // lp2 can neither expire early nor claim once expired.
+    assert!(pool.expire(&lp2.pubkey()).await.is_err());
+    pool.warp(MONTH).await;
+    assert!(pool.claim(&lp2).await.is_err());
+    pool.expire(&lp2.pubkey()).await.unwrap();
+    pool.assert_invariants().await;
+
+    pool.warp(MONTH).await;
+    pool.expire(&lp3.pubkey()).await.unwrap();
+    pool.assert_invariants().await;
+
+    // Every premium has vested to underwriters and nothing stays locked.
+    let pool_config = pool.pool_config().await;
+    assert_eq!(pool_config.locked_shares, 0);
+    assert_eq!(pool.token_balance(pool.premium_vault).await, 0);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/il_protection.so; run by anchor test and CI"]
+async fn claim_votes_escrow_shares_until_withdrawn() {
+    let mut pool = Pool::new().await;
+    let alice = pool.wallet(1_000 * TOKEN).await;
+    let bob = pool.wallet(1_000 * TOKEN).await;
+    pool.stake(&alice, 100 * TOKEN).await.unwrap();
+    pool.stake(&bob, 300 * TOKEN).await.unwrap();
+    pool.enable_review(DAY).await.unwrap();
+
+    let lp = pool.wallet(100 * TOKEN).await;
+    pool.buy(&lp, 50 * TOKEN, MONTH).await.unwrap();
+    let claim = pool.submit(&lp).await.unwrap();
+    pool.assert_invariants().await;
+
+    // Votes below quorum leave the claim pending and cannot be pulled early.
+    let alice_shares = pool.shares_of(&alice.pubkey()).await;
+    pool.vote(&alice, claim, alice_shares / 2).await.unwrap();
+    assert_eq!(
+        pool.shares_of(&alice.pubkey()).await,
+        alice_shares - alice_shares / 2
+    );
+    pool.assert_invariants().await;
+    assert!(pool.withdraw_vote(&alice, claim).await.is_err());
+
+    // Bob's shares carry the vote past quorum and reject the claim.
+    let bob_shares = pool.shares_of(&bob.pubkey()).await;
+    pool.vote(&bob, claim, bob_shares / 2).await.unwrap();
+    let rejected: Claim = pool.account(claim).await;
+    assert!(rejected.status == ClaimStatus::Rejected);
+    pool.assert_invariants().await;
+
+    // A resolved claim releases every vote.
+    pool.withdraw_vote(&alice, claim).await.unwrap();
+    pool.withdraw_vote(&bob, claim).await.unwrap();
+    assert_eq!(pool.shares_of(&alice.pubkey()).await, alice_shares);
+    assert_eq!(pool.shares_of(&bob.pubkey()).await, bob_shares);
+    pool.assert_invariants().await;
+
+    // A vote on a claim nobody resolves comes back once the window closes.
+    let resubmitted = pool.submit(&lp).await.unwrap();
+    pool.vote(&alice, resubmitted, TOKEN).await.unwrap();
+    pool.assert_invariants().await;
+    pool.warp(DAY).await;
+    pool.withdraw_vote(&alice, resubmitted).await.unwrap();
+    assert_eq!(pool.shares_of(&alice.pubkey()).await, alice_shares);
+    pool.assert_invariants().await;
+}
@@ -33,6 +33,8 @@ describe("il-protection", () => {
   const poolId = new anchor.BN(1);
   const premiumRate = 500; // 5% (500 basis points)
   const thresholdMax = 2000; // 20% (2000 basis points)
+  const withdrawalCooldown = new anchor.BN(0); // complete withdrawals right away in tests
+  const cancellationFeeBps = 1000; // 10% of the unused premium
   let lpOwner1Ata: PublicKey;
   let lpOwner2Ata: PublicKey;
   let lpOwner3Ata: PublicKey;
@@ -46,10 +48,14 @@ describe("il-protection", () => {
   // PDAs
   let poolConfigPda: PublicKey;
   let poolVaultPda: PublicKey;
+  let shareMintPda: PublicKey;
+  let premiumVaultPda: PublicKey;
   let underwriter1StakePda: PublicKey;
   let underwriter2StakePda: PublicKey;
   let underwriter1Ata: PublicKey;
   let underwriter2Ata: PublicKey;
+  let underwriter1ShareAta: PublicKey;
+  let underwriter2ShareAta: PublicKey;
 
   console.log(`payer ${payer.publicKey.toString()}`);
 
@@ -90,17 +96,37 @@ describe("il-protection", () => {
       ASSOCIATED_TOKEN_PROGRAM_ID
     );
     console.log(`poolVaultPda ${poolVaultPda.toString()}`);
+
+    shareMintPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint"), poolConfigPda.toBuffer()],
+      program.programId
+    )[0];
+    console.log(`shareMintPda ${shareMintPda.toString()}`);
+
+    premiumVaultPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("premium_vault"), poolConfigPda.toBuffer()],
+      program.programId
+    )[0];
+    console.log(`premiumVaultPda ${premiumVaultPda.toString()}`);
   });
 
   describe("InitializePool", () => {
     it("Successfully initializes a pool", async () => {
       try {
         const initializePoolIx = await program.methods
-          .initializePool(poolId, premiumRate, thresholdMax)
+          .initializePool(
+            poolId,
+            premiumRate,
+            thresholdMax,
+            withdrawalCooldown,
+            cancellationFeeBps
+          )
           .accountsStrict({
             poolConfig: poolConfigPda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             mint: mint,
+            shareMint: shareMintPda,
             signer: payer.publicKey,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -176,11 +202,19 @@ describe("il-protection", () => {
       await new Promise((resolve) => setTimeout(resolve, 500));
       try {
         const initializePoolIx = await program.methods
-          .initializePool(poolId, premiumRate, thresholdMax)
+          .initializePool(
+            poolId,
+            premiumRate,
+            thresholdMax,
+            withdrawalCooldown,
+            cancellationFeeBps
+          )
           .accountsStrict({
             poolConfig: poolConfigPda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             mint: mint,
+            shareMint: shareMintPda,
             signer: payer.publicKey,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -219,13 +253,31 @@ describe("il-protection", () => {
         ASSOCIATED_TOKEN_PROGRAM_ID
       );
 
+      const newShareMintPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("share_mint"), newPoolConfigPda.toBuffer()],
+        program.programId
+      )[0];
+
+      const newPremiumVaultPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("premium_vault"), newPoolConfigPda.toBuffer()],
+        program.programId
+      )[0];
+
       try {
         const initializePoolIx = await program.methods
-          .initializePool(newPoolId, newPremiumRate, newThresholdMax)
+          .initializePool(
+            newPoolId,
+            newPremiumRate,
+            newThresholdMax,
+            withdrawalCooldown,
+            cancellationFeeBps
+          )
           .accountsStrict({
             poolConfig: newPoolConfigPda,
             poolVault: newPoolVaultPda,
+            premiumVault: newPremiumVaultPda,
             mint: mint,
+            shareMint: newShareMintPda,
             signer: payer.publicKey,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
             associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -265,6 +317,152 @@ describe("il-protection", () => {
     });
   });
 
+  describe("PoolAdmin", () => {
+    const updatePoolAccounts = (admin: PublicKey) => ({
+      admin,
+      poolConfig: poolConfigPda,
+    });
+
+    it("Stores the pool creator as admin", async () => {
+      const poolConfig = await program.account.poolConfig.fetch(poolConfigPda);
+      assert.equal(
+        poolConfig.admin.toString(),
+        payer.publicKey.toString(),
+        "Admin should be the pool creator"
+      );
+      assert.equal(poolConfig.paused, false, "Pool should start unpaused");
+    });
+
+    it("Fails to initialize a pool with basis points above 10_000", async () => {
+      const badPoolId = new anchor.BN(3);
+      const badPoolConfigPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("pool_config"), badPoolId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      )[0];
+
+      try {
+        const initializePoolIx = await program.methods
+          .initializePool(
+            badPoolId,
+            10_001,
+            thresholdMax,
+            withdrawalCooldown,
+            cancellationFeeBps
+          )
+          .accountsStrict({
+            poolConfig: badPoolConfigPda,
+            poolVault: getAssociatedTokenAddressSync(
+              mint,
+              badPoolConfigPda,
+              true,
+              TOKEN_2022_PROGRAM_ID,
+              ASSOCIATED_TOKEN_PROGRAM_ID
+            ),
+            premiumVault: PublicKey.findProgramAddressSync(
+              [Buffer.from("premium_vault"), badPoolConfigPda.toBuffer()],
+              program.programId
+            )[0],
+            mint: mint,
+            shareMint: PublicKey.findProgramAddressSync(
+              [Buffer.from("share_mint"), badPoolConfigPda.toBuffer()],
+              program.programId
+            )[0],
+            signer: payer.publicKey,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction();
+        await createAndSendV0Tx([initializePoolIx]);
+        assert.fail("Should have failed with invalid basis points");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("InvalidBasisPoints"),
+          "Should fail with InvalidBasisPoints error"
+        );
+      }
+    });
+
+    it("Admin updates pool parameters", async () => {
+      const updateIx = await program.methods
+        .updateParams(600, 2500, new anchor.BN(3600), 500)
+        .accountsStrict(updatePoolAccounts(payer.publicKey))
+        .instruction();
+      await createAndSendV0Tx([updateIx]);
+
+      const updated = await program.account.poolConfig.fetch(poolConfigPda);
+      assert.equal(updated.premiumRate, 600, "Premium rate should update");
+      assert.equal(updated.thresholdMax, 2500, "Threshold max should update");
+      assert.equal(
+        updated.withdrawalCooldown.toString(),
+        "3600",
+        "Withdrawal cooldown should update"
+      );
+      assert.equal(
+        updated.cancellationFeeBps,
+        500,
+        "Cancellation fee should update"
+      );
+
+      // Restore the parameters the rest of the suite relies on
+      const restoreIx = await program.methods
+        .updateParams(
+          premiumRate,
+          thresholdMax,
+          withdrawalCooldown,
+          cancellationFeeBps
+        )
+        .accountsStrict(updatePoolAccounts(payer.publicKey))
+        .instruction();
+      await createAndSendV0Tx([restoreIx]);
+    });
+
+    it("Fails to update parameters from a non-admin", async () => {
+      const notAdmin = Keypair.generate();
+      try {
+        const updateIx = await program.methods
+          .updateParams(
+            100,
+            thresholdMax,
+            withdrawalCooldown,
+            cancellationFeeBps
+          )
+          .accountsStrict(updatePoolAccounts(notAdmin.publicKey))
+          .signers([notAdmin])
+          .instruction();
+        await createAndSendV0Tx([updateIx], [notAdmin]);
+        assert.fail("Should have failed with unauthorized admin");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("Unauthorized"),
+          "Should fail with Unauthorized error"
+        );
+      }
+    });
+
+    it("Admin pauses and unpauses the pool", async () => {
+      const pauseIx = await program.methods
+        .pause()
+        .accountsStrict(updatePoolAccounts(payer.publicKey))
+        .instruction();
+      await createAndSendV0Tx([pauseIx]);
+
+      let poolConfig = await program.account.poolConfig.fetch(poolConfigPda);
+      assert.equal(poolConfig.paused, true, "Pool should be paused");
+
+      const unpauseIx = await program.methods
+        .unpause()
+        .accountsStrict(updatePoolAccounts(payer.publicKey))
+        .instruction();
+      await createAndSendV0Tx([unpauseIx]);
+
+      poolConfig = await program.account.poolConfig.fetch(poolConfigPda);
+      assert.equal(poolConfig.paused, false, "Pool should be unpaused");
+    });
+  });
+
   describe("StakeCollateral", () => {
     before(async () => {
       // Derive underwriter stake PDAs
@@ -309,6 +507,21 @@ describe("il-protection", () => {
       );
       console.log(`underwriter2Ata ${underwriter2Ata.toString()}`);
 
+      underwriter1ShareAta = getAssociatedTokenAddressSync(
+        shareMintPda,
+        underwriter1.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+      underwriter2ShareAta = getAssociatedTokenAddressSync(
+        shareMintPda,
+        underwriter2.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+
       // Mint tokens to underwriters
       await mintTo(
         connection,
@@ -348,10 +561,14 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             underwriterStake: underwriter1StakePda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
+            shareMint: shareMintPda,
             underwriter: underwriter1.publicKey,
             underwriterAta: underwriter1Ata,
+            underwriterShareAta: underwriter1ShareAta,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
           .signers([underwriter1, payer.payer])
@@ -374,9 +591,11 @@ describe("il-protection", () => {
       console.log(`Underwriter Stake:`);
       console.log(`  underwriter: ${underwriterStake.underwriter.toString()}`);
       console.log(`  pool_config: ${underwriterStake.poolConfig.toString()}`);
-      console.log(`  shares: ${underwriterStake.shares.toString()}`);
       console.log(`  bump: ${underwriterStake.bump}`);
 
+      const shares = await shareBalance(underwriter1ShareAta);
+      console.log(`  shares: ${shares.toString()}`);
+
       // Verify pool config updated
       const poolConfig = await program.account.poolConfig.fetch(poolConfigPda);
       console.log(`Pool total_shares: ${poolConfig.totalShares.toString()}`);
@@ -393,7 +612,7 @@ describe("il-protection", () => {
         "Pool config should match"
       );
       assert.equal(
-        underwriterStake.shares.toString(),
+        shares.toString(),
         stakeAmount.toString(),
         "Shares should equal stake amount for first deposit"
       );
@@ -416,10 +635,7 @@ describe("il-protection", () => {
 
     it("Successfully adds more collateral to existing stake", async () => {
       const additionalAmount = 50_000_000; // 50 tokens
-      const previousStake = await program.account.underwriterStake.fetch(
-        underwriter1StakePda
-      );
-      const previousShares = previousStake.shares;
+      const previousShares = await shareBalance(underwriter1ShareAta);
       const previousPoolConfig = await program.account.poolConfig.fetch(
         poolConfigPda
       );
@@ -433,10 +649,14 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             underwriterStake: underwriter1StakePda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
+            shareMint: shareMintPda,
             underwriter: underwriter1.publicKey,
             underwriterAta: underwriter1Ata,
+            underwriterShareAta: underwriter1ShareAta,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
           .signers([underwriter1, payer.payer])
@@ -453,21 +673,19 @@ describe("il-protection", () => {
       }
 
       // Fetch updated stake
-      const updatedStake = await program.account.underwriterStake.fetch(
-        underwriter1StakePda
-      );
+      const updatedShares = await shareBalance(underwriter1ShareAta);
       const updatedPoolConfig = await program.account.poolConfig.fetch(
         poolConfigPda
       );
 
-      console.log(`Updated shares: ${updatedStake.shares.toString()}`);
+      console.log(`Updated shares: ${updatedShares.toString()}`);
       console.log(
         `Updated total shares: ${updatedPoolConfig.totalShares.toString()}`
       );
 
       // Shares should increase proportionally
       assert.ok(
-        updatedStake.shares.gt(previousShares),
+        updatedShares.gt(previousShares),
         "Shares should increase"
       );
       assert.ok(
@@ -495,10 +713,14 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             underwriterStake: underwriter2StakePda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
+            shareMint: shareMintPda,
             underwriter: underwriter2.publicKey,
             underwriterAta: underwriter2Ata,
+            underwriterShareAta: underwriter2ShareAta,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
           .signers([underwriter2, payer.payer])
@@ -521,9 +743,8 @@ describe("il-protection", () => {
         poolConfigPda
       );
 
-      console.log(
-        `Underwriter2 shares: ${underwriter2Stake.shares.toString()}`
-      );
+      const underwriter2Shares = await shareBalance(underwriter2ShareAta);
+      console.log(`Underwriter2 shares: ${underwriter2Shares.toString()}`);
       console.log(
         `Pool total shares: ${updatedPoolConfig.totalShares.toString()}`
       );
@@ -540,9 +761,10 @@ describe("il-protection", () => {
         underwriter2.publicKey.toString(),
         "Underwriter2 should match"
       );
-      assert.ok(
-        underwriter2Stake.shares.gt(new anchor.BN(0)),
-        "Should have shares"
+      assert.equal(
+        underwriter2Shares.toString(),
+        expectedShares.toString(),
+        "Should receive shares at the current share price"
       );
       assert.ok(
         updatedPoolConfig.totalShares.gt(previousTotalShares),
@@ -559,10 +781,14 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             underwriterStake: underwriter1StakePda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
+            shareMint: shareMintPda,
             underwriter: underwriter1.publicKey,
             underwriterAta: underwriter1Ata,
+            underwriterShareAta: underwriter1ShareAta,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
           .signers([underwriter1, payer.payer])
@@ -589,10 +815,14 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             underwriterStake: underwriter1StakePda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
+            shareMint: shareMintPda,
             underwriter: underwriter1.publicKey,
             underwriterAta: underwriter1Ata,
+            underwriterShareAta: underwriter1ShareAta,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
           .signers([underwriter1, payer.payer])
@@ -707,6 +937,29 @@ describe("il-protection", () => {
       console.log("LP owners funded with tokens");
     });
 
+    it("Quotes a premium before buying", async () => {
+      const coverageAmount = new anchor.BN(50_000_000);
+      const duration = new anchor.BN(2592000);
+
+      const atMax = await program.methods
+        .quotePremium(thresholdMax, coverageAmount, duration)
+        .accountsStrict({ poolConfig: poolConfigPda, poolVault: poolVaultPda })
+        .view();
+      const lowThreshold = await program.methods
+        .quotePremium(500, coverageAmount, duration)
+        .accountsStrict({ poolConfig: poolConfigPda, poolVault: poolVaultPda })
+        .view();
+
+      console.log(`Premium at threshold_max: ${atMax.toString()}`);
+      console.log(`Premium at 5% threshold: ${lowThreshold.toString()}`);
+
+      assert.ok(atMax.gt(new anchor.BN(0)), "Premium should be positive");
+      assert.ok(
+        lowThreshold.gt(atMax),
+        "Lower thresholds should cost more than threshold_max"
+      );
+    });
+
     it("Successfully buys protection", async () => {
       const threshold = 1000; // 10%
       const coverageAmount = 50_000_000; // 50 tokens
@@ -730,6 +983,7 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             policy: policy1Pda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
             lpOwnerAta: lpOwner1Ata,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -816,6 +1070,7 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             policy: policy2Pda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
             lpOwnerAta: lpOwner2Ata,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -868,6 +1123,7 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             policy: invalidPolicyPda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
             lpOwnerAta: lpOwner3Ata,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -912,6 +1168,7 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             policy: invalidPolicyPda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
             lpOwnerAta: lpOwner3Ata,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -947,6 +1204,7 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             policy: policy1Pda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
             lpOwnerAta: lpOwner1Ata,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -967,39 +1225,118 @@ describe("il-protection", () => {
     });
   });
 
-  describe("WithdrawCollateral", () => {
-    it("Successfully withdraws collateral", async () => {
+  describe("Withdrawal", () => {
+    // Queued shares are escrowed under the underwriter's stake account.
+    const withdrawalEscrow = (stakePda: PublicKey) =>
+      getAssociatedTokenAddressSync(
+        shareMintPda,
+        stakePda,
+        true,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+
+    const completeWithdrawalIx = (
+      withdrawer: Keypair,
+      withdrawerStakePda: PublicKey,
+      withdrawerAta: PublicKey,
+      withdrawerShareAta: PublicKey
+    ) =>
+      program.methods
+        .completeWithdrawal()
+        .accountsStrict({
+          payer: payer.publicKey,
+          poolConfig: poolConfigPda,
+          underwriterStake: withdrawerStakePda,
+          poolVault: poolVaultPda,
+          poolMint: mint,
+          shareMint: shareMintPda,
+          underwriter: withdrawer.publicKey,
+          underwriterAta: withdrawerAta,
+          underwriterShareAta: withdrawerShareAta,
+          withdrawalEscrow: withdrawalEscrow(withdrawerStakePda),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([withdrawer, payer.payer])
+        .instruction();
+
+    it("Fails to complete a withdrawal that was never requested", async () => {
+      try {
+        const ix = await completeWithdrawalIx(
+          underwriter1,
+          underwriter1StakePda,
+          underwriter1Ata,
+          underwriter1ShareAta
+        );
+        await createAndSendV0Tx([ix], [underwriter1]);
+        assert.fail("Should have failed without a pending withdrawal");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("NoPendingWithdrawal"),
+          "Should fail with NoPendingWithdrawal error"
+        );
+      }
+    });
+
+    it("Successfully requests and completes a withdrawal", async () => {
       const withdrawer = underwriter1;
       const withdrawerAta = underwriter1Ata;
       const withdrawerStakePda = underwriter1StakePda;
+      const withdrawerShareAta = underwriter1ShareAta;
 
-      const stakeBefore = await program.account.underwriterStake.fetch(
-        withdrawerStakePda
-      );
+      const sharesBefore = await shareBalance(withdrawerShareAta);
       const poolConfigBefore = await program.account.poolConfig.fetch(
         poolConfigPda
       );
 
-      // Now withdraw
-      const withdrawAmount = 1_000_000; // 1 tokens
+      const withdrawShares = new anchor.BN(1_000_000);
 
       try {
-        const withdrawCollateralIx = await program.methods
-          .withdrawCollateral(new anchor.BN(withdrawAmount))
+        const requestWithdrawalIx = await program.methods
+          .requestWithdrawal(withdrawShares)
           .accountsStrict({
-            payer: payer.publicKey,
+            underwriter: withdrawer.publicKey,
             poolConfig: poolConfigPda,
             underwriterStake: withdrawerStakePda,
-            poolVault: poolVaultPda,
-            poolMint: mint,
-            underwriter: withdrawer.publicKey,
-            underwriterAta: withdrawerAta,
+            shareMint: shareMintPda,
+            underwriterShareAta: withdrawerShareAta,
+            withdrawalEscrow: withdrawalEscrow(withdrawerStakePda),
             tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
-          .signers([withdrawer, payer.payer])
+          .signers([withdrawer])
           .instruction();
-        await createAndSendV0Tx([withdrawCollateralIx], [withdrawer]);
+        await createAndSendV0Tx([requestWithdrawalIx], [withdrawer]);
+
+        const pending = await program.account.underwriterStake.fetch(
+          withdrawerStakePda
+        );
+        assert.equal(
+          pending.pendingShares.toString(),
+          withdrawShares.toString(),
+          "Requested shares should be queued"
+        );
+        assert.equal(
+          (await shareBalance(withdrawerShareAta)).toString(),
+          sharesBefore.sub(withdrawShares).toString(),
+          "Queued shares should move out of the wallet"
+        );
+        assert.equal(
+          (await shareBalance(withdrawalEscrow(withdrawerStakePda))).toString(),
+          withdrawShares.toString(),
+          "Queued shares should be escrowed until completion"
+        );
+
+        const ix = await completeWithdrawalIx(
+          withdrawer,
+          withdrawerStakePda,
+          withdrawerAta,
+          withdrawerShareAta
+        );
+        await createAndSendV0Tx([ix], [withdrawer]);
       } catch (error: any) {
         console.error(`Error withdrawing collateral: ${error}`);
         if (error.logs && Array.isArray(error.logs)) {
@@ -1016,44 +1353,91 @@ describe("il-protection", () => {
         poolConfigPda
       );
 
-      console.log(`Shares before: ${stakeBefore.shares.toString()}`);
-      console.log(`Shares after: ${stakeAfter.shares.toString()}`);
-      console.log(
-        `Total shares before: ${poolConfigBefore.totalShares.toString()}`
+      const sharesAfter = await shareBalance(withdrawerShareAta);
+
+      console.log(`Shares before: ${sharesBefore.toString()}`);
+      console.log(`Shares after: ${sharesAfter.toString()}`);
+
+      assert.equal(
+        sharesAfter.toString(),
+        sharesBefore.sub(withdrawShares).toString(),
+        "Share tokens should be burned for the queued amount"
       );
-      console.log(
-        `Total shares after: ${poolConfigAfter.totalShares.toString()}`
+      assert.equal(
+        stakeAfter.pendingShares.toString(),
+        "0",
+        "Pending shares should be cleared"
+      );
+      assert.equal(
+        poolConfigAfter.totalShares.toString(),
+        poolConfigBefore.totalShares.sub(withdrawShares).toString(),
+        "Total shares should decrease by the queued amount"
       );
+    });
 
-      assert.ok(
-        stakeAfter.shares.lt(stakeBefore.shares),
-        "Shares should decrease"
+    it("Cancels a queued withdrawal and returns the shares", async () => {
+      const sharesBefore = await shareBalance(underwriter1ShareAta);
+      const requestIx = await program.methods
+        .requestWithdrawal(new anchor.BN(1_000_000))
+        .accountsStrict({
+          underwriter: underwriter1.publicKey,
+          poolConfig: poolConfigPda,
+          underwriterStake: underwriter1StakePda,
+          shareMint: shareMintPda,
+          underwriterShareAta: underwriter1ShareAta,
+          withdrawalEscrow: withdrawalEscrow(underwriter1StakePda),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([underwriter1])
+        .instruction();
+      const cancelIx = await program.methods
+        .cancelWithdrawal()
+        .accountsStrict({
+          underwriter: underwriter1.publicKey,
+          poolConfig: poolConfigPda,
+          underwriterStake: underwriter1StakePda,
+          shareMint: shareMintPda,
+          underwriterShareAta: underwriter1ShareAta,
+          withdrawalEscrow: withdrawalEscrow(underwriter1StakePda),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([underwriter1])
+        .instruction();
+      await createAndSendV0Tx([requestIx], [underwriter1]);
+      await createAndSendV0Tx([cancelIx], [underwriter1]);
+
+      const stake = await program.account.underwriterStake.fetch(
+        underwriter1StakePda
       );
-      assert.ok(
-        poolConfigAfter.totalShares.lt(poolConfigBefore.totalShares),
-        "Total shares should decrease"
+      assert.equal(stake.pendingShares.toString(), "0");
+      assert.equal(
+        (await shareBalance(underwriter1ShareAta)).toString(),
+        sharesBefore.toString(),
+        "Cancelled shares should return to the wallet"
       );
     });
 
-    it("Fails to withdraw zero amount", async () => {
+    it("Fails to request a withdrawal of zero shares", async () => {
       try {
-        const withdrawCollateralIx = await program.methods
-          .withdrawCollateral(new anchor.BN(0))
+        const requestWithdrawalIx = await program.methods
+          .requestWithdrawal(new anchor.BN(0))
           .accountsStrict({
-            payer: payer.publicKey,
+            underwriter: underwriter1.publicKey,
             poolConfig: poolConfigPda,
             underwriterStake: underwriter1StakePda,
-            poolVault: poolVaultPda,
-            poolMint: mint,
-            underwriter: underwriter1.publicKey,
-            underwriterAta: underwriter1Ata,
+            shareMint: shareMintPda,
+            underwriterShareAta: underwriter1ShareAta,
+            withdrawalEscrow: withdrawalEscrow(underwriter1StakePda),
             tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
-          .signers([underwriter1, payer.payer])
+          .signers([underwriter1])
           .instruction();
-        await createAndSendV0Tx([withdrawCollateralIx], [underwriter1]);
-        assert.fail("Should have failed with zero amount");
+        await createAndSendV0Tx([requestWithdrawalIx], [underwriter1]);
+        assert.fail("Should have failed with zero shares");
       } catch (error: any) {
         console.log(`Expected error: ${error.message}`);
         assert.ok(
@@ -1063,51 +1447,38 @@ describe("il-protection", () => {
       }
     });
 
-    it("Fails to withdraw more than unlocked shares", async () => {
-      // underwriter1 has staked but also has locked shares from policies
-      // Try to withdraw more than their unlocked shares
-      const excessiveAmount = new anchor.BN(1_000_000_000); // Very large amount
+    it("Fails to request more than unlocked shares", async () => {
+      const excessiveShares = new anchor.BN(1_000_000_000); // Very large amount
 
       try {
-        const withdrawCollateralIx = await program.methods
-          .withdrawCollateral(excessiveAmount)
+        const requestWithdrawalIx = await program.methods
+          .requestWithdrawal(excessiveShares)
           .accountsStrict({
-            payer: payer.publicKey,
+            underwriter: underwriter1.publicKey,
             poolConfig: poolConfigPda,
             underwriterStake: underwriter1StakePda,
-            poolVault: poolVaultPda,
-            poolMint: mint,
-            underwriter: underwriter1.publicKey,
-            underwriterAta: underwriter1Ata,
+            shareMint: shareMintPda,
+            underwriterShareAta: underwriter1ShareAta,
+            withdrawalEscrow: withdrawalEscrow(underwriter1StakePda),
             tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
-          .signers([underwriter1, payer.payer])
+          .signers([underwriter1])
           .instruction();
-        await createAndSendV0Tx([withdrawCollateralIx], [underwriter1]);
+        await createAndSendV0Tx([requestWithdrawalIx], [underwriter1]);
         assert.fail("Should have failed with not enough shares");
       } catch (error: any) {
         console.log(`Expected error: ${error.message}`);
         assert.ok(
-          error.message.includes("NotEnoughShares") ||
-            error.message.includes("SharesZero"),
-          "Should fail with NotEnoughShares or SharesZero error"
+          error.message.includes("NotEnoughShares"),
+          "Should fail with NotEnoughShares error"
         );
       }
     });
 
-    it("Fails to withdraw when underwriter has no stake", async () => {
+    it("Fails to request a withdrawal when underwriter has no stake", async () => {
       const noStaker = Keypair.generate();
-      await new Promise((resolve) => setTimeout(resolve, 500));
-
-      const noStakerAta = await createAssociatedTokenAccount(
-        connection,
-        payer.payer,
-        mint,
-        noStaker.publicKey,
-        null,
-        TOKEN_2022_PROGRAM_ID
-      );
 
       const noStakerStakePda = PublicKey.findProgramAddressSync(
         [
@@ -1118,29 +1489,36 @@ describe("il-protection", () => {
         program.programId
       )[0];
 
+      const noStakerShareAta = getAssociatedTokenAddressSync(
+        shareMintPda,
+        noStaker.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+
       try {
-        const withdrawCollateralIx = await program.methods
-          .withdrawCollateral(new anchor.BN(10_000_000))
+        const requestWithdrawalIx = await program.methods
+          .requestWithdrawal(new anchor.BN(10_000_000))
           .accountsStrict({
-            payer: payer.publicKey,
+            underwriter: noStaker.publicKey,
             poolConfig: poolConfigPda,
             underwriterStake: noStakerStakePda,
-            poolVault: poolVaultPda,
-            poolMint: mint,
-            underwriter: noStaker.publicKey,
-            underwriterAta: noStakerAta,
+            shareMint: shareMintPda,
+            underwriterShareAta: noStakerShareAta,
+            withdrawalEscrow: withdrawalEscrow(noStakerStakePda),
             tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
           })
-          .signers([noStaker, payer.payer])
+          .signers([noStaker])
           .instruction();
-        await createAndSendV0Tx([withdrawCollateralIx], [noStaker]);
+        await createAndSendV0Tx([requestWithdrawalIx], [noStaker]);
         assert.fail("Should have failed with no shares");
       } catch (error: any) {
         console.log(`Expected error: ${error.message}`);
         assert.ok(
-          error.message.includes("SharesZero") ||
-            error.message.includes("AccountNotInitialized") ||
+          error.message.includes("AccountNotInitialized") ||
             error.message.includes("Account does not exist")
         );
       }
@@ -1236,6 +1614,7 @@ describe("il-protection", () => {
           poolConfig: poolConfigPda,
           policy: claimPolicy1Pda,
           poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
           poolMint: mint,
           lpOwnerAta: claimant1Ata,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -1255,6 +1634,7 @@ describe("il-protection", () => {
           poolConfig: poolConfigPda,
           policy: claimPolicy2Pda,
           poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
           poolMint: mint,
           lpOwnerAta: claimant2Ata,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -1293,6 +1673,7 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             policy: claimPolicy1Pda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
             lpOwnerAta: claimant1Ata,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -1364,6 +1745,7 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             policy: claimPolicy2Pda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
             lpOwnerAta: claimant2Ata,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -1435,6 +1817,7 @@ describe("il-protection", () => {
           poolConfig: poolConfigPda,
           policy: newPolicyPda,
           poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
           poolMint: mint,
           lpOwnerAta: newClaimantAta,
           tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -1443,8 +1826,8 @@ describe("il-protection", () => {
         .signers([newClaimant, payer.payer])
         .instruction();
 
-      // Try to claim with threshold that doesn't exceed maximum (20%)
-      const invalidThreshold = 1500; // 15%, does not exceed 20%
+      // Try to claim with IL that doesn't exceed the policy's 10% threshold
+      const invalidThreshold = 1000;
 
       try {
         const claimProtectionIx = await program.methods
@@ -1455,6 +1838,7 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             policy: newPolicyPda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
             lpOwnerAta: newClaimantAta,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -1488,6 +1872,7 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             policy: claimPolicy1Pda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
             lpOwnerAta: claimant1Ata,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -1538,6 +1923,7 @@ describe("il-protection", () => {
             poolConfig: poolConfigPda,
             policy: nonExistentPolicyPda,
             poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
             poolMint: mint,
             lpOwnerAta: nonExistentAta,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
@@ -1558,6 +1944,1323 @@ describe("il-protection", () => {
     });
   });
 
+  describe("ClaimReview", () => {
+    const reviewClaimant = Keypair.generate();
+    const challengeWindow = new anchor.BN(3600); // 1 hour
+    const autoSettleLimit = new anchor.BN(10_000_000); // 10 tokens
+    let reviewClaimantAta: PublicKey;
+    let reviewPolicyPda: PublicKey;
+    let reviewClaimPda: PublicKey;
+
+    const claimProtectionIx = () =>
+      program.methods
+        .claimProtection(2500)
+        .accountsStrict({
+          payer: payer.publicKey,
+          lpOwner: reviewClaimant.publicKey,
+          poolConfig: poolConfigPda,
+          policy: reviewPolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          lpOwnerAta: reviewClaimantAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([reviewClaimant, payer.payer])
+        .instruction();
+
+    before(async () => {
+      reviewClaimantAta = await createAssociatedTokenAccount(
+        connection,
+        payer.payer,
+        mint,
+        reviewClaimant.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      await mintTo(
+        connection,
+        payer.payer,
+        mint,
+        reviewClaimantAta,
+        payer.publicKey,
+        500_000_000,
+        [],
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      reviewPolicyPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          poolConfigPda.toBuffer(),
+          reviewClaimant.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      reviewClaimPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("claim"),
+          reviewPolicyPda.toBuffer(),
+          new anchor.BN(0).toArrayLike(Buffer, "le", 4),
+        ],
+        program.programId
+      )[0];
+
+      const configIx = await program.methods
+        .updateClaimsConfig(payer.publicKey, challengeWindow, autoSettleLimit)
+        .accountsStrict({ admin: payer.publicKey, poolConfig: poolConfigPda })
+        .instruction();
+
+      const buyProtectionIx = await program.methods
+        .buyProtection(1000, new anchor.BN(50_000_000), new anchor.BN(2592000))
+        .accountsStrict({
+          payer: payer.publicKey,
+          lpOwner: reviewClaimant.publicKey,
+          poolConfig: poolConfigPda,
+          policy: reviewPolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          lpOwnerAta: reviewClaimantAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([reviewClaimant, payer.payer])
+        .instruction();
+      await createAndSendV0Tx([configIx, buyProtectionIx], [reviewClaimant]);
+    });
+
+    after(async () => {
+      const resetIx = await program.methods
+        .updateClaimsConfig(PublicKey.default, new anchor.BN(0), new anchor.BN(0))
+        .accountsStrict({ admin: payer.publicKey, poolConfig: poolConfigPda })
+        .instruction();
+      await createAndSendV0Tx([resetIx]);
+    });
+
+    it("Fails to auto-settle a claim above the limit", async () => {
+      try {
+        const ix = await claimProtectionIx();
+        await createAndSendV0Tx([ix], [reviewClaimant]);
+        assert.fail("Should have failed with ClaimRequiresReview");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("ClaimRequiresReview"),
+          "Should fail with ClaimRequiresReview error"
+        );
+      }
+    });
+
+    it("Submits a claim and opens the challenge window", async () => {
+      const submitClaimIx = await program.methods
+        .submitClaim(2500)
+        .accountsStrict({
+          payer: payer.publicKey,
+          lpOwner: reviewClaimant.publicKey,
+          poolConfig: poolConfigPda,
+          policy: reviewPolicyPda,
+          claim: reviewClaimPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([reviewClaimant, payer.payer])
+        .instruction();
+      await createAndSendV0Tx([submitClaimIx], [reviewClaimant]);
+
+      const claim = await program.account.claim.fetch(reviewClaimPda);
+      const policy = await program.account.policy.fetch(reviewPolicyPda);
+
+      assert.ok("pending" in claim.status, "Claim should be pending");
+      assert.equal(
+        claim.challengeEndsAt.toString(),
+        claim.submittedAt.add(challengeWindow).toString(),
+        "Challenge window should start at submission"
+      );
+      assert.equal(
+        claim.amount.toString(),
+        policy.coverageAmount.toString(),
+        "Claim amount should be the policy coverage"
+      );
+      assert.equal(policy.claimPending, true, "Policy should be under review");
+      assert.equal(claim.nonce, 0, "First claim should use nonce 0");
+      assert.equal(
+        policy.claimsSubmitted,
+        1,
+        "Next claim should get a new address"
+      );
+    });
+
+    it("Fails to finalize before the challenge window closes", async () => {
+      try {
+        const finalizeClaimIx = await program.methods
+          .finalizeClaim()
+          .accountsStrict({
+            payer: payer.publicKey,
+            lpOwner: reviewClaimant.publicKey,
+            poolConfig: poolConfigPda,
+            policy: reviewPolicyPda,
+            claim: reviewClaimPda,
+            poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
+            poolMint: mint,
+            lpOwnerAta: reviewClaimantAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .instruction();
+        await createAndSendV0Tx([finalizeClaimIx]);
+        assert.fail("Should have failed with ChallengeWindowOpen");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("ChallengeWindowOpen"),
+          "Should fail with ChallengeWindowOpen error"
+        );
+      }
+    });
+
+    it("Assessor rejects the claim and it can be closed", async () => {
+      const rejectClaimIx = await program.methods
+        .rejectClaim()
+        .accountsStrict({
+          assessor: payer.publicKey,
+          poolConfig: poolConfigPda,
+          policy: reviewPolicyPda,
+          claim: reviewClaimPda,
+        })
+        .instruction();
+      await createAndSendV0Tx([rejectClaimIx]);
+
+      const claim = await program.account.claim.fetch(reviewClaimPda);
+      const policy = await program.account.policy.fetch(reviewPolicyPda);
+      assert.ok("rejected" in claim.status, "Claim should be rejected");
+      assert.equal(
+        policy.claimPending,
+        false,
+        "Policy should no longer be under review"
+      );
+
+      const closeClaimIx = await program.methods
+        .closeClaim()
+        .accountsStrict({
+          lpOwner: reviewClaimant.publicKey,
+          poolConfig: poolConfigPda,
+          claim: reviewClaimPda,
+        })
+        .instruction();
+      await createAndSendV0Tx([closeClaimIx]);
+
+      const closed = await connection.getAccountInfo(reviewClaimPda);
+      assert.isNull(closed, "Claim account should be closed");
+    });
+  });
+
+  describe("UnderwriterPnl", () => {
+    it("Values an underwriter's shares against their entry price", async () => {
+      const pnl = await program.methods
+        .underwriterPnl()
+        .accountsStrict({
+          underwriter: underwriter1.publicKey,
+          poolConfig: poolConfigPda,
+          underwriterStake: underwriter1StakePda,
+          poolVault: poolVaultPda,
+          shareMint: shareMintPda,
+          underwriterShareAta: underwriter1ShareAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .view();
+
+      const stake = await program.account.underwriterStake.fetch(
+        underwriter1StakePda
+      );
+      assert.equal(
+        pnl.shares.toString(),
+        (await shareBalance(underwriter1ShareAta))
+          .add(stake.pendingShares)
+          .toString()
+      );
+      assert.equal(
+        pnl.untrackedShares.toString(),
+        "0",
+        "Staked shares should all have an entry price"
+      );
+      assert.ok(pnl.entrySharePrice.gtn(0), "Entry price should be recorded");
+      const expected = pnl.shares
+        .mul(pnl.sharePrice.sub(pnl.entrySharePrice))
+        .div(new anchor.BN(1_000_000_000));
+      assert.ok(
+        pnl.unrealizedPnl.sub(expected).abs().lten(2),
+        "Unrealized PnL should be the share price move on held shares"
+      );
+    });
+  });
+
+  describe("PremiumReserve", () => {
+    const reserveOwner = Keypair.generate();
+    const duration = new anchor.BN(3); // seconds
+    let reserveOwnerAta: PublicKey;
+    let reservePolicyPda: PublicKey;
+
+    const expirePolicyIx = () =>
+      program.methods
+        .expirePolicy()
+        .accountsStrict({
+          lpOwner: reserveOwner.publicKey,
+          poolConfig: poolConfigPda,
+          policy: reservePolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .instruction();
+
+    before(async () => {
+      reserveOwnerAta = await createAssociatedTokenAccount(
+        connection,
+        payer.payer,
+        mint,
+        reserveOwner.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      await mintTo(
+        connection,
+        payer.payer,
+        mint,
+        reserveOwnerAta,
+        payer.publicKey,
+        100_000_000,
+        [],
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      reservePolicyPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          poolConfigPda.toBuffer(),
+          reserveOwner.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+    });
+
+    it("Holds the premium in the reserve instead of the pool vault", async () => {
+      const poolVaultBefore = await shareBalance(poolVaultPda);
+      const reserveBefore = await shareBalance(premiumVaultPda);
+
+      let purchased: any;
+      const listener = program.addEventListener(
+        "policyPurchased",
+        (event) => (purchased = event)
+      );
+
+      const buyProtectionIx = await program.methods
+        .buyProtection(1000, new anchor.BN(10_000_000), duration)
+        .accountsStrict({
+          payer: payer.publicKey,
+          lpOwner: reserveOwner.publicKey,
+          poolConfig: poolConfigPda,
+          policy: reservePolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          lpOwnerAta: reserveOwnerAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([reserveOwner, payer.payer])
+        .instruction();
+      await createAndSendV0Tx([buyProtectionIx], [reserveOwner]);
+      await new Promise((resolve) => setTimeout(resolve, 1000)); // Wait for logs
+      await program.removeEventListener(listener);
+
+      const policy = await program.account.policy.fetch(reservePolicyPda);
+      assert.ok(policy.premium.gt(new anchor.BN(0)), "Premium should be set");
+      assert.ok(purchased, "PolicyPurchased should be emitted");
+      assert.equal(
+        purchased.premium.toString(),
+        policy.premium.toString(),
+        "Event premium should match the policy"
+      );
+      assert.equal(
+        purchased.lockedShares.toString(),
+        policy.lockedShares.toString(),
+        "Event locked shares should match the policy"
+      );
+      assert.equal(
+        policy.premiumVested.toString(),
+        "0",
+        "Nothing should be vested yet"
+      );
+      assert.equal(
+        (await shareBalance(premiumVaultPda)).sub(reserveBefore).toString(),
+        policy.premium.toString(),
+        "Premium should sit in the reserve"
+      );
+      assert.equal(
+        (await shareBalance(poolVaultPda)).toString(),
+        poolVaultBefore.toString(),
+        "Pool vault should not receive unearned premium"
+      );
+    });
+
+    it("Fails to expire a policy before its expiry", async () => {
+      try {
+        await createAndSendV0Tx([await expirePolicyIx()]);
+        assert.fail("Should have failed before expiry");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("PolicyNotExpired"),
+          "Should fail with PolicyNotExpired error"
+        );
+      }
+    });
+
+    it("Vests the remaining premium and unlocks shares on expiry", async () => {
+      await new Promise((resolve) => setTimeout(resolve, 4000));
+
+      const policy = await program.account.policy.fetch(reservePolicyPda);
+      const poolConfigBefore = await program.account.poolConfig.fetch(
+        poolConfigPda
+      );
+      const poolVaultBefore = await shareBalance(poolVaultPda);
+
+      const releaseIx = await program.methods
+        .releasePremium()
+        .accountsStrict({
+          poolConfig: poolConfigPda,
+          policy: reservePolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .instruction();
+      await createAndSendV0Tx([releaseIx, await expirePolicyIx()]);
+
+      const poolConfigAfter = await program.account.poolConfig.fetch(
+        poolConfigPda
+      );
+      assert.equal(
+        (await shareBalance(poolVaultPda)).sub(poolVaultBefore).toString(),
+        policy.premium.sub(policy.premiumVested).toString(),
+        "Unvested premium should move to the pool vault"
+      );
+      assert.equal(
+        poolConfigBefore.lockedShares.sub(poolConfigAfter.lockedShares).toString(),
+        policy.lockedShares.toString(),
+        "Policy shares should be unlocked"
+      );
+      assert.isNull(
+        await connection.getAccountInfo(reservePolicyPda),
+        "Policy account should be closed"
+      );
+    });
+  });
+
+  describe("LpProtection", () => {
+    const lpHolder = Keypair.generate();
+
+    it("Fails to insure a position in an account not owned by amm-program", async () => {
+      const lpPolicyPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          poolConfigPda.toBuffer(),
+          lpHolder.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      const lpHolderAta = await createAssociatedTokenAccount(
+        connection,
+        payer.payer,
+        mint,
+        lpHolder.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      try {
+        const buyLpProtectionIx = await program.methods
+          .buyLpProtection(1000, new anchor.BN(1_000_000), new anchor.BN(2592000))
+          .accountsStrict({
+            protection: {
+              lpOwner: lpHolder.publicKey,
+              payer: payer.publicKey,
+              poolConfig: poolConfigPda,
+              policy: lpPolicyPda,
+              poolVault: poolVaultPda,
+              premiumVault: premiumVaultPda,
+              poolMint: mint,
+              lpOwnerAta: lpHolderAta,
+              tokenProgram: TOKEN_2022_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+            },
+            ammConfig: poolConfigPda,
+            ammOracle: PublicKey.findProgramAddressSync(
+              [
+                Buffer.from("amm_oracle"),
+                poolConfigPda.toBuffer(),
+                poolConfigPda.toBuffer(),
+              ],
+              program.programId
+            )[0],
+            ammVaultX: poolVaultPda,
+            ammVaultY: poolVaultPda,
+            mintLp: shareMintPda,
+            lpOwnerLpAta: lpHolderAta,
+            lpEscrow: getAssociatedTokenAddressSync(
+              shareMintPda,
+              lpPolicyPda,
+              true,
+              TOKEN_2022_PROGRAM_ID,
+              ASSOCIATED_TOKEN_PROGRAM_ID
+            ),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([lpHolder, payer.payer])
+          .instruction();
+        await createAndSendV0Tx([buyLpProtectionIx], [lpHolder]);
+        assert.fail("Should have failed with a foreign AMM config");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("AccountOwnedByWrongProgram"),
+          "Should reject an AMM config not owned by amm-program"
+        );
+      }
+    });
+  });
+
+  describe("CancelPolicy", () => {
+    const canceller = Keypair.generate();
+    let cancellerAta: PublicKey;
+    let cancelPolicyPda: PublicKey;
+
+    before(async () => {
+      cancellerAta = await createAssociatedTokenAccount(
+        connection,
+        payer.payer,
+        mint,
+        canceller.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(
+        connection,
+        payer.payer,
+        mint,
+        cancellerAta,
+        payer.publicKey,
+        100_000_000,
+        [],
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      cancelPolicyPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          poolConfigPda.toBuffer(),
+          canceller.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+      const buyProtectionIx = await program.methods
+        .buyProtection(1000, new anchor.BN(10_000_000), new anchor.BN(2592000))
+        .accountsStrict({
+          payer: payer.publicKey,
+          lpOwner: canceller.publicKey,
+          poolConfig: poolConfigPda,
+          policy: cancelPolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          lpOwnerAta: cancellerAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([canceller, payer.payer])
+        .instruction();
+      await createAndSendV0Tx([buyProtectionIx], [canceller]);
+    });
+
+    it("Refunds the unused premium minus the fee and unlocks shares", async () => {
+      const policy = await program.account.policy.fetch(cancelPolicyPda);
+      const poolConfigBefore = await program.account.poolConfig.fetch(
+        poolConfigPda
+      );
+      const lpBefore = await shareBalance(cancellerAta);
+      const poolVaultBefore = await shareBalance(poolVaultPda);
+
+      const cancelPolicyIx = await program.methods
+        .cancelPolicy()
+        .accountsStrict({
+          lpOwner: canceller.publicKey,
+          poolConfig: poolConfigPda,
+          policy: cancelPolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          lpOwnerAta: cancellerAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([canceller])
+        .instruction();
+      await createAndSendV0Tx([cancelPolicyIx], [canceller]);
+
+      const refund = (await shareBalance(cancellerAta)).sub(lpBefore);
+      const kept = (await shareBalance(poolVaultPda)).sub(poolVaultBefore);
+      const poolConfigAfter = await program.account.poolConfig.fetch(
+        poolConfigPda
+      );
+
+      assert.ok(refund.gt(new anchor.BN(0)), "LP should get a refund");
+      assert.ok(
+        refund.lt(policy.premium.muln(9).divn(10).addn(1)),
+        "Refund should be at most 90% of the premium"
+      );
+      assert.equal(
+        refund.add(kept).toString(),
+        policy.premium.toString(),
+        "Refund plus earned premium and fee should equal the premium"
+      );
+      assert.equal(
+        poolConfigBefore.lockedShares.sub(poolConfigAfter.lockedShares).toString(),
+        policy.lockedShares.toString(),
+        "Policy shares should be unlocked"
+      );
+      assert.isNull(
+        await connection.getAccountInfo(cancelPolicyPda),
+        "Policy account should be closed"
+      );
+    });
+  });
+
+  describe("PolicyTransfer", () => {
+    const seller = Keypair.generate();
+    const buyer = Keypair.generate();
+    let sellerAta: PublicKey;
+    let sellerPolicyPda: PublicKey;
+    let buyerPolicyPda: PublicKey;
+
+    before(async () => {
+      sellerAta = await createAssociatedTokenAccount(
+        connection,
+        payer.payer,
+        mint,
+        seller.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(
+        connection,
+        payer.payer,
+        mint,
+        sellerAta,
+        payer.publicKey,
+        100_000_000,
+        [],
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      sellerPolicyPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          poolConfigPda.toBuffer(),
+          seller.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      buyerPolicyPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          poolConfigPda.toBuffer(),
+          buyer.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+      const buyProtectionIx = await program.methods
+        .buyProtection(1000, new anchor.BN(10_000_000), new anchor.BN(2592000))
+        .accountsStrict({
+          payer: payer.publicKey,
+          lpOwner: seller.publicKey,
+          poolConfig: poolConfigPda,
+          policy: sellerPolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          lpOwnerAta: sellerAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([seller, payer.payer])
+        .instruction();
+      await createAndSendV0Tx([buyProtectionIx], [seller]);
+    });
+
+    it("Moves the coverage to the new owner", async () => {
+      const before = await program.account.policy.fetch(sellerPolicyPda);
+      assert.equal(before.lpOwner.toString(), seller.publicKey.toString());
+
+      const transferPolicyIx = await program.methods
+        .transferPolicy()
+        .accountsStrict({
+          lpOwner: seller.publicKey,
+          newOwner: buyer.publicKey,
+          payer: payer.publicKey,
+          poolConfig: poolConfigPda,
+          policy: sellerPolicyPda,
+          newPolicy: buyerPolicyPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([seller, payer.payer])
+        .instruction();
+      await createAndSendV0Tx([transferPolicyIx], [seller]);
+
+      const after = await program.account.policy.fetch(buyerPolicyPda);
+      assert.equal(
+        after.lpOwner.toString(),
+        buyer.publicKey.toString(),
+        "New owner should hold the policy"
+      );
+      assert.equal(
+        after.coverageAmount.toString(),
+        before.coverageAmount.toString(),
+        "Coverage should carry over"
+      );
+      assert.equal(
+        after.lockedShares.toString(),
+        before.lockedShares.toString(),
+        "Locked shares should carry over"
+      );
+      assert.equal(
+        after.expiryTime.toString(),
+        before.expiryTime.toString(),
+        "Expiry should carry over"
+      );
+      assert.isNull(
+        await connection.getAccountInfo(sellerPolicyPda),
+        "Old policy account should be closed"
+      );
+    });
+  });
+
+  describe("Reinsurance", () => {
+    const reinsurerPoolId = new anchor.BN(2);
+    const lossOwner = Keypair.generate();
+    const attachment = new anchor.BN(10_000_000); // 10 tokens retained
+    const limit = new anchor.BN(20_000_000); // 20 tokens of cover
+    let reinsurerPoolPda: PublicKey;
+    let reinsurerVaultPda: PublicKey;
+    let reinsurerPremiumVaultPda: PublicKey;
+    let treatyPda: PublicKey;
+    let lossOwnerAta: PublicKey;
+    let lossPolicyPda: PublicKey;
+
+    const buyReinsuranceIx = (
+      admin: PublicKey,
+      reinsurerAdmin: PublicKey = payer.publicKey
+    ) =>
+      program.methods
+        .buyReinsurance(attachment, limit, new anchor.BN(3600))
+        .accountsStrict({
+          admin,
+          reinsurerAdmin,
+          payer: payer.publicKey,
+          primaryPool: poolConfigPda,
+          reinsurerPool: reinsurerPoolPda,
+          treaty: treatyPda,
+          primaryVault: poolVaultPda,
+          reinsurerVault: reinsurerVaultPda,
+          reinsurerPremiumVault: reinsurerPremiumVaultPda,
+          poolMint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+
+    before(async () => {
+      reinsurerPoolPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("pool_config"),
+          reinsurerPoolId.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      )[0];
+      reinsurerVaultPda = getAssociatedTokenAddressSync(
+        mint,
+        reinsurerPoolPda,
+        true,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+      reinsurerPremiumVaultPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("premium_vault"), reinsurerPoolPda.toBuffer()],
+        program.programId
+      )[0];
+      treatyPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("treaty"),
+          poolConfigPda.toBuffer(),
+          reinsurerPoolPda.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+      // Capitalise the reinsurer pool
+      const reinsurerShareMintPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("share_mint"), reinsurerPoolPda.toBuffer()],
+        program.programId
+      )[0];
+      const stakeIx = await program.methods
+        .stakeCollateral(new anchor.BN(100_000_000))
+        .accountsStrict({
+          payer: payer.publicKey,
+          poolConfig: reinsurerPoolPda,
+          underwriterStake: PublicKey.findProgramAddressSync(
+            [
+              Buffer.from("underwriter"),
+              reinsurerPoolId.toArrayLike(Buffer, "le", 8),
+              underwriter2.publicKey.toBuffer(),
+            ],
+            program.programId
+          )[0],
+          poolVault: reinsurerVaultPda,
+          premiumVault: reinsurerPremiumVaultPda,
+          poolMint: mint,
+          shareMint: reinsurerShareMintPda,
+          underwriter: underwriter2.publicKey,
+          underwriterAta: underwriter2Ata,
+          underwriterShareAta: getAssociatedTokenAddressSync(
+            reinsurerShareMintPda,
+            underwriter2.publicKey,
+            false,
+            TOKEN_2022_PROGRAM_ID,
+            ASSOCIATED_TOKEN_PROGRAM_ID
+          ),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([underwriter2, payer.payer])
+        .instruction();
+      await createAndSendV0Tx([stakeIx], [underwriter2]);
+
+      lossOwnerAta = await createAssociatedTokenAccount(
+        connection,
+        payer.payer,
+        mint,
+        lossOwner.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(
+        connection,
+        payer.payer,
+        mint,
+        lossOwnerAta,
+        payer.publicKey,
+        100_000_000,
+        [],
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+      lossPolicyPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          poolConfigPda.toBuffer(),
+          lossOwner.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+    });
+
+    it("Fails to buy reinsurance without the primary pool admin", async () => {
+      try {
+        await createAndSendV0Tx(
+          [await buyReinsuranceIx(underwriter1.publicKey)],
+          [underwriter1]
+        );
+        assert.fail("Should have failed with a non-admin signer");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("Unauthorized"),
+          "Should fail with Unauthorized error"
+        );
+      }
+    });
+
+    it("Fails to buy reinsurance without the reinsurer pool admin", async () => {
+      try {
+        await createAndSendV0Tx(
+          [await buyReinsuranceIx(payer.publicKey, underwriter1.publicKey)],
+          [underwriter1]
+        );
+        assert.fail("Should have failed with a non-admin reinsurer signer");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("Unauthorized"),
+          "Should fail with Unauthorized error"
+        );
+      }
+    });
+
+    it("Buys an excess-of-loss layer from another pool", async () => {
+      const primaryBefore = await shareBalance(poolVaultPda);
+      const reserveBefore = await shareBalance(reinsurerPremiumVaultPda);
+      const reinsurerBefore = await program.account.poolConfig.fetch(
+        reinsurerPoolPda
+      );
+
+      await createAndSendV0Tx([await buyReinsuranceIx(payer.publicKey)]);
+
+      const treaty = await program.account.reinsuranceTreaty.fetch(treatyPda);
+      const reinsurerAfter = await program.account.poolConfig.fetch(
+        reinsurerPoolPda
+      );
+      assert.equal(treaty.attachment.toString(), attachment.toString());
+      assert.equal(treaty.limit.toString(), limit.toString());
+      assert.ok(treaty.premium.gt(new anchor.BN(0)), "Premium should be set");
+      assert.equal(
+        primaryBefore.sub(await shareBalance(poolVaultPda)).toString(),
+        treaty.premium.toString(),
+        "Primary pool should pay the premium"
+      );
+      assert.equal(
+        (await shareBalance(reinsurerPremiumVaultPda))
+          .sub(reserveBefore)
+          .toString(),
+        treaty.premium.toString(),
+        "Premium should flow to the reinsurer's reserve"
+      );
+      assert.equal(
+        reinsurerAfter.lockedShares.sub(reinsurerBefore.lockedShares).toString(),
+        treaty.lockedShares.toString(),
+        "Reinsurer shares should back the limit"
+      );
+    });
+
+    it("Recovers primary losses above the attachment", async () => {
+      const coverage = new anchor.BN(30_000_000);
+      const buyProtectionIx = await program.methods
+        .buyProtection(1000, coverage, new anchor.BN(2592000))
+        .accountsStrict({
+          payer: payer.publicKey,
+          lpOwner: lossOwner.publicKey,
+          poolConfig: poolConfigPda,
+          policy: lossPolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          lpOwnerAta: lossOwnerAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([lossOwner, payer.payer])
+        .instruction();
+      const claimProtectionIx = await program.methods
+        .claimProtection(2500)
+        .accountsStrict({
+          payer: payer.publicKey,
+          lpOwner: lossOwner.publicKey,
+          poolConfig: poolConfigPda,
+          policy: lossPolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          lpOwnerAta: lossOwnerAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([lossOwner, payer.payer])
+        .instruction();
+      await createAndSendV0Tx([buyProtectionIx, claimProtectionIx], [lossOwner]);
+
+      const primaryBefore = await shareBalance(poolVaultPda);
+
+      const recoverIx = await program.methods
+        .recoverReinsurance()
+        .accountsStrict({
+          primaryPool: poolConfigPda,
+          reinsurerPool: reinsurerPoolPda,
+          treaty: treatyPda,
+          primaryVault: poolVaultPda,
+          reinsurerVault: reinsurerVaultPda,
+          reinsurerPremiumVault: reinsurerPremiumVaultPda,
+          poolMint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .instruction();
+      await createAndSendV0Tx([recoverIx]);
+
+      // 30 tokens lost, 10 retained, capped at the 20 token limit
+      const treaty = await program.account.reinsuranceTreaty.fetch(treatyPda);
+      assert.equal(treaty.recovered.toString(), limit.toString());
+      const primary = await program.account.poolConfig.fetch(poolConfigPda);
+      assert.equal(
+        primary.reinsuranceRecovered.toString(),
+        limit.toString(),
+        "Primary pool should track the recovery across treaties"
+      );
+      assert.equal(
+        (await shareBalance(poolVaultPda)).sub(primaryBefore).toString(),
+        limit.toString(),
+        "Primary pool should receive the recovery"
+      );
+    });
+
+    it("Fails to expire a treaty before its expiry", async () => {
+      try {
+        const expireTreatyIx = await program.methods
+          .expireTreaty()
+          .accountsStrict({
+            admin: payer.publicKey,
+            primaryPool: poolConfigPda,
+            reinsurerPool: reinsurerPoolPda,
+            treaty: treatyPda,
+            primaryVault: poolVaultPda,
+            reinsurerVault: reinsurerVaultPda,
+            reinsurerPremiumVault: reinsurerPremiumVaultPda,
+            poolMint: mint,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .instruction();
+        await createAndSendV0Tx([expireTreatyIx]);
+        assert.fail("Should have failed before expiry");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("TreatyNotExpired"),
+          "Should fail with TreatyNotExpired error"
+        );
+      }
+    });
+  });
+
+  describe("CollateralBasket", () => {
+    const haircutBps = 200; // value the second stablecoin at 98%
+    const basketUnderwriter = Keypair.generate();
+    const basketLp = Keypair.generate();
+    let basketMint: PublicKey;
+    let collateralAssetPda: PublicKey;
+    let basketVaultPda: PublicKey;
+    let basketUnderwriterAta: PublicKey;
+    let basketUnderwriterStakePda: PublicKey;
+    let basketUnderwriterShareAta: PublicKey;
+    let basketLpAta: PublicKey;
+    let basketLpSettlementAta: PublicKey;
+    let basketPolicyPda: PublicKey;
+
+    before(async () => {
+      const basketMintKeypair = Keypair.generate();
+      await createMint(
+        connection,
+        payer.payer,
+        payer.publicKey,
+        payer.publicKey,
+        6, // same decimals as the pool mint
+        basketMintKeypair,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+      basketMint = basketMintKeypair.publicKey;
+
+      collateralAssetPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("collateral"),
+          poolConfigPda.toBuffer(),
+          basketMint.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      basketVaultPda = getAssociatedTokenAddressSync(
+        basketMint,
+        poolConfigPda,
+        true,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+      basketUnderwriterStakePda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("underwriter"),
+          poolId.toArrayLike(Buffer, "le", 8),
+          basketUnderwriter.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      basketUnderwriterShareAta = getAssociatedTokenAddressSync(
+        shareMintPda,
+        basketUnderwriter.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      );
+      basketPolicyPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("policy"),
+          poolConfigPda.toBuffer(),
+          basketLp.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+      basketUnderwriterAta = await createAssociatedTokenAccount(
+        connection,
+        payer.payer,
+        basketMint,
+        basketUnderwriter.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(
+        connection,
+        payer.payer,
+        basketMint,
+        basketUnderwriterAta,
+        payer.publicKey,
+        100_000_000,
+        [],
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      basketLpAta = await createAssociatedTokenAccount(
+        connection,
+        payer.payer,
+        mint,
+        basketLp.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(
+        connection,
+        payer.payer,
+        mint,
+        basketLpAta,
+        payer.publicKey,
+        100_000_000,
+        [],
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+      basketLpSettlementAta = await createAssociatedTokenAccount(
+        connection,
+        payer.payer,
+        basketMint,
+        basketLp.publicKey,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      );
+    });
+
+    const basketAccounts = () => [
+      { pubkey: collateralAssetPda, isSigner: false, isWritable: false },
+      { pubkey: basketVaultPda, isSigner: false, isWritable: false },
+    ];
+
+    it("Adds a second stablecoin with a haircut", async () => {
+      const addCollateralAssetIx = await program.methods
+        .addCollateralAsset(haircutBps)
+        .accountsStrict({
+          admin: payer.publicKey,
+          poolConfig: poolConfigPda,
+          collateralAsset: collateralAssetPda,
+          vault: basketVaultPda,
+          mint: basketMint,
+          poolMint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+      await createAndSendV0Tx([addCollateralAssetIx]);
+
+      const asset = await program.account.collateralAsset.fetch(
+        collateralAssetPda
+      );
+      const poolConfig = await program.account.poolConfig.fetch(poolConfigPda);
+      assert.equal(asset.mint.toString(), basketMint.toString());
+      assert.equal(asset.vault.toString(), basketVaultPda.toString());
+      assert.equal(asset.haircutBps, haircutBps);
+      assert.equal(poolConfig.collateralAssets, 1);
+    });
+
+    it("Fails to price shares without the basket", async () => {
+      try {
+        const stakeCollateralIx = await program.methods
+          .stakeCollateral(new anchor.BN(1_000_000))
+          .accountsStrict({
+            payer: payer.publicKey,
+            poolConfig: poolConfigPda,
+            underwriterStake: underwriter1StakePda,
+            poolVault: poolVaultPda,
+            premiumVault: premiumVaultPda,
+            poolMint: mint,
+            shareMint: shareMintPda,
+            underwriter: underwriter1.publicKey,
+            underwriterAta: underwriter1Ata,
+            underwriterShareAta: underwriter1ShareAta,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([underwriter1, payer.payer])
+          .instruction();
+        await createAndSendV0Tx([stakeCollateralIx], [underwriter1]);
+        assert.fail("Should have failed without the basket");
+      } catch (error: any) {
+        console.log(`Expected error: ${error.message}`);
+        assert.ok(
+          error.message.includes("BasketMismatch"),
+          "Should fail with BasketMismatch error"
+        );
+      }
+    });
+
+    it("Mints shares for the haircut value of a basket deposit", async () => {
+      const amount = new anchor.BN(100_000_000);
+      const poolConfigBefore = await program.account.poolConfig.fetch(
+        poolConfigPda
+      );
+      const poolAssets = (await shareBalance(poolVaultPda)).add(
+        (await shareBalance(basketVaultPda))
+          .muln(10_000 - haircutBps)
+          .divn(10_000)
+      );
+
+      const stakeBasketCollateralIx = await program.methods
+        .stakeBasketCollateral(amount)
+        .accountsStrict({
+          payer: payer.publicKey,
+          poolConfig: poolConfigPda,
+          underwriterStake: basketUnderwriterStakePda,
+          collateralAsset: collateralAssetPda,
+          assetVault: basketVaultPda,
+          assetMint: basketMint,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          shareMint: shareMintPda,
+          underwriter: basketUnderwriter.publicKey,
+          underwriterAta: basketUnderwriterAta,
+          underwriterShareAta: basketUnderwriterShareAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(basketAccounts())
+        .signers([basketUnderwriter, payer.payer])
+        .instruction();
+      await createAndSendV0Tx([stakeBasketCollateralIx], [basketUnderwriter]);
+
+      const expectedShares = amount
+        .muln(10_000 - haircutBps)
+        .divn(10_000)
+        .mul(poolConfigBefore.totalShares)
+        .div(poolAssets);
+      assert.equal(
+        (await shareBalance(basketUnderwriterShareAta)).toString(),
+        expectedShares.toString(),
+        "Shares should be minted for the haircut value"
+      );
+      assert.equal(
+        (await shareBalance(basketVaultPda)).toString(),
+        amount.toString(),
+        "Deposit should be held in the basket vault"
+      );
+    });
+
+    it("Pays a claim in the policy's settlement mint", async () => {
+      const coverage = new anchor.BN(20_000_000);
+      const buyProtectionIx = await program.methods
+        .buyProtection(1000, coverage, new anchor.BN(2592000))
+        .accountsStrict({
+          payer: payer.publicKey,
+          lpOwner: basketLp.publicKey,
+          poolConfig: poolConfigPda,
+          policy: basketPolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          lpOwnerAta: basketLpAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(basketAccounts())
+        .signers([basketLp, payer.payer])
+        .instruction();
+      const setSettlementMintIx = await program.methods
+        .setSettlementMint()
+        .accountsStrict({
+          lpOwner: basketLp.publicKey,
+          poolConfig: poolConfigPda,
+          policy: basketPolicyPda,
+          settlementMint: basketMint,
+          collateralAsset: collateralAssetPda,
+        })
+        .signers([basketLp])
+        .instruction();
+      await createAndSendV0Tx([buyProtectionIx, setSettlementMintIx], [basketLp]);
+
+      const policy = await program.account.policy.fetch(basketPolicyPda);
+      assert.equal(policy.settlementMint.toString(), basketMint.toString());
+
+      const basketVaultBefore = await shareBalance(basketVaultPda);
+      const claimProtectionIx = await program.methods
+        .claimProtection(2500)
+        .accountsStrict({
+          payer: payer.publicKey,
+          lpOwner: basketLp.publicKey,
+          poolConfig: poolConfigPda,
+          policy: basketPolicyPda,
+          poolVault: poolVaultPda,
+          premiumVault: premiumVaultPda,
+          poolMint: mint,
+          lpOwnerAta: basketLpAta,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: collateralAssetPda, isSigner: false, isWritable: false },
+          { pubkey: basketVaultPda, isSigner: false, isWritable: true },
+          { pubkey: basketMint, isSigner: false, isWritable: false },
+          { pubkey: basketLpSettlementAta, isSigner: false, isWritable: true },
+        ])
+        .signers([basketLp, payer.payer])
+        .instruction();
+      await createAndSendV0Tx([claimProtectionIx], [basketLp]);
+
+      assert.equal(
+        (await shareBalance(basketLpSettlementAta)).toString(),
+        coverage.toString(),
+        "Coverage should be paid in the settlement mint"
+      );
+      assert.equal(
+        basketVaultBefore.sub(await shareBalance(basketVaultPda)).toString(),
+        coverage.toString(),
+        "Payout should come from the basket vault"
+      );
+    });
+  });
+
+  async function shareBalance(ata: PublicKey): Promise<anchor.BN> {
+    const balance = await connection.getTokenAccountBalance(ata);
+    return new anchor.BN(balance.value.amount);
+  }
+
   async function createAndSendV0Tx(
     txInstructions: anchor.web3.TransactionInstruction[],
     signers: anchor.web3.Signer[] = []
@@ -0,0 +1,150 @@
+use crate::{error::StakeError, FreezeAuthority};
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_asset_plugin,
+    instructions::{
+        AddPluginV1CpiBuilder, ApprovePluginAuthorityV1CpiBuilder, RemovePluginV1CpiBuilder,
+        RevokePluginAuthorityV1CpiBuilder, UpdatePluginV1CpiBuilder,
+    },
+    types::{
+        Attribute, Attributes, FreezeDelegate, Plugin, PluginAuthority, PluginType, UpdateAuthority,
+    },
+    ID as CORE_PROGRAM_ID,
+};
+
+/// Rejects accounts that are not mpl-core assets in `collection` held by
+/// `owner`.
+pub fn verify_asset(asset: &AccountInfo, collection: &Pubkey, owner: &Pubkey) -> Result<()> {
+    require_keys_eq!(*asset.owner, CORE_PROGRAM_ID, StakeError::InvalidAsset);
+    require!(!asset.data_is_empty(), StakeError::AssetNotInitialized);
+
+    let base =
+        BaseAssetV1::from_bytes(&asset.try_borrow_data()?).map_err(|_| StakeError::InvalidAsset)?;
+    require!(
+        base.update_authority == UpdateAuthority::Collection(*collection),
+        StakeError::AssetNotInCollection
+    );
+    require_keys_eq!(base.owner, *owner, StakeError::NotOwner);
+    Ok(())
+}
+
+/// Entries of the asset's Attributes plugin, empty if it has none.
+pub fn attributes(asset: &AccountInfo) -> Vec<Attribute> {
+    fetch_asset_plugin::<Attributes>(asset, PluginType::Attributes)
+        .map(|(_, attributes, _)| attributes.attribute_list)
+        .unwrap_or_default()
+}
+
+/// Accounts shared by the mpl-core CPIs that freeze and thaw staked assets.
+pub struct Core<'info> {
+    pub core_program: AccountInfo<'info>,
+    pub collection: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> Core<'info> {
+    /// Freezes `asset` under a FreezeDelegate held by `stake_account`. An
+    /// existing, unfrozen FreezeDelegate is handed to `stake_account` and
+    /// updated rather than added again; its authority is returned so `thaw`
+    /// can hand it back.
+    pub fn freeze(
+        &self,
+        asset: &AccountInfo<'info>,
+        stake_account: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<Option<FreezeAuthority>> {
+        let authority = PluginAuthority::Address {
+            address: stake_account.key(),
+        };
+
+        let Ok((prior, existing, _)) =
+            fetch_asset_plugin::<FreezeDelegate>(asset, PluginType::FreezeDelegate)
+        else {
+            AddPluginV1CpiBuilder::new(&self.core_program)
+                .asset(asset)
+                .collection(Some(&self.collection))
+                .payer(&self.payer)
+                .authority(None)
+                .system_program(&self.system_program)
+                .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: true }))
+                .init_authority(authority)
+                .invoke()?;
+            return Ok(None);
+        };
+        require!(!existing.frozen, StakeError::AssetFrozen);
+
+        ApprovePluginAuthorityV1CpiBuilder::new(&self.core_program)
+            .asset(asset)
+            .collection(Some(&self.collection))
+            .payer(&self.payer)
+            .authority(None)
+            .system_program(&self.system_program)
+            .plugin_type(PluginType::FreezeDelegate)
+            .new_authority(authority)
+            .invoke()?;
+
+        UpdatePluginV1CpiBuilder::new(&self.core_program)
+            .asset(asset)
+            .collection(Some(&self.collection))
+            .payer(&self.payer)
+            .authority(Some(stake_account))
+            .system_program(&self.system_program)
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: true }))
+            .invoke_signed(signer_seeds)?;
+        Ok(Some(prior.into()))
+    }
+
+    /// Thaws `asset` as `stake_account`, then hands the FreezeDelegate back to
+    /// `prior`, its authority before staking, or removes it if staking added
+    /// it. Frozen assets cannot be staked, so thawed is also its prior state.
+    pub fn thaw(
+        &self,
+        asset: &AccountInfo<'info>,
+        stake_account: &AccountInfo<'info>,
+        signer_seeds: &[&[&[u8]]],
+        prior: Option<FreezeAuthority>,
+    ) -> Result<()> {
+        UpdatePluginV1CpiBuilder::new(&self.core_program)
+            .asset(asset)
+            .collection(Some(&self.collection))
+            .payer(&self.payer)
+            .authority(Some(stake_account))
+            .system_program(&self.system_program)
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+            .invoke_signed(signer_seeds)?;
+
+        match prior {
+            None => RemovePluginV1CpiBuilder::new(&self.core_program)
+                .asset(asset)
+                .collection(Some(&self.collection))
+                .payer(&self.payer)
+                .authority(None)
+                .system_program(&self.system_program)
+                .plugin_type(PluginType::FreezeDelegate)
+                .invoke()?,
+            // Revoking resets an owner-managed plugin to the owner.
+            Some(FreezeAuthority::Owner) => {
+                RevokePluginAuthorityV1CpiBuilder::new(&self.core_program)
+                    .asset(asset)
+                    .collection(Some(&self.collection))
+                    .payer(&self.payer)
+                    .authority(None)
+                    .system_program(&self.system_program)
+                    .plugin_type(PluginType::FreezeDelegate)
+                    .invoke()?
+            }
+            Some(authority) => ApprovePluginAuthorityV1CpiBuilder::new(&self.core_program)
+                .asset(asset)
+                .collection(Some(&self.collection))
+                .payer(&self.payer)
+                .authority(None)
+                .system_program(&self.system_program)
+                .plugin_type(PluginType::FreezeDelegate)
+                .new_authority(authority.into())
+                .invoke()?,
+        }
+        Ok(())
+    }
+}
@@ -2,3 +2,13 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const SEED: &str = "anchor";
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+pub const MAX_COLLECTIONS: usize = 8;
+
+pub const MAX_MULTIPLIERS: usize = 8;
+
+pub const BASE_MULTIPLIER_BPS: u16 = 10_000;
+
+pub const ACC_PRECISION: u128 = 1_000_000_000_000;
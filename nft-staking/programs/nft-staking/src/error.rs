@@ -31,4 +31,40 @@ pub enum StakeError {
 
     #[msg("Not enough points to claim")]
     NotEnoughPoints,
+
+    #[msg("Collection Not Allowed")]
+    CollectionNotAllowed,
+
+    #[msg("Asset Not In Collection")]
+    AssetNotInCollection,
+
+    #[msg("Too Many Collections")]
+    TooManyCollections,
+
+    #[msg("Invalid Multiplier")]
+    InvalidMultiplier,
+
+    #[msg("Batch must hold asset and stake account pairs")]
+    InvalidBatch,
+
+    #[msg("Invalid Stake Account")]
+    InvalidStakeAccount,
+
+    #[msg("Not Admin")]
+    NotAdmin,
+
+    #[msg("Staking Is Paused")]
+    Paused,
+
+    #[msg("Staking Is Not Paused")]
+    NotPaused,
+
+    #[msg("Invalid Emission Schedule")]
+    InvalidEmission,
+
+    #[msg("Overflow")]
+    Overflow,
+
+    #[msg("Asset Already Frozen")]
+    AssetFrozen,
 }
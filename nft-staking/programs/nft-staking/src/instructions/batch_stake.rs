@@ -0,0 +1,200 @@
+use crate::{
+    asset::{attributes, verify_asset, Core},
+    error::StakeError,
+    CollectionInfo, LockTier, StakeAccount, StakeConfig, UserAccount,
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{
+        allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+    },
+};
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+#[derive(Accounts)]
+pub struct BatchStake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = collection.owner == &CORE_PROGRAM_ID @ StakeError::InvalidCollection,
+        constraint = !collection.data_is_empty() @ StakeError::CollectionNotInitialized
+    )]
+    /// CHECK: Verified by mpl-core
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"collection_info", collection.key().as_ref()],
+        bump = collection_info.bump,
+        constraint = stake_config.collections.contains(&collection_info.key()) @ StakeError::CollectionNotAllowed
+    )]
+    pub collection_info: Account<'info, CollectionInfo>,
+
+    #[account(
+        mut,
+        seeds = [b"config", stake_config.seed.to_le_bytes().as_ref()],
+        bump = stake_config.bump,
+        constraint = !stake_config.paused @ StakeError::Paused
+    )]
+    pub stake_config: Account<'info, StakeConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), stake_config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: this account is checked by the address constraint
+    pub core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BatchStake<'info> {
+    /// Stakes every `(asset, stake_account)` pair in `pairs` under the same
+    /// `lock_tier`, settling the user's rewards once beforehand.
+    pub fn batch_stake(&mut self, lock_tier: LockTier, pairs: &[AccountInfo<'info>]) -> Result<()> {
+        require!(
+            !pairs.is_empty() && pairs.len() % 2 == 0,
+            StakeError::InvalidBatch
+        );
+        let count = pairs.len() / 2;
+        require!(
+            self.user_account.amount_staked as usize + count
+                <= self.stake_config.max_stake as usize,
+            StakeError::MaxStakeReached
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        self.stake_config.update_rewards(now)?;
+        self.user_account.settle(&self.stake_config)?;
+
+        let core = self.core();
+        let space = StakeAccount::DISCRIMINATOR.len() + StakeAccount::INIT_SPACE;
+        let rent = Rent::get()?.minimum_balance(space);
+        let config = self.stake_config.key();
+        for pair in pairs.chunks(2) {
+            let (asset, stake_account) = (&pair[0], &pair[1]);
+            verify_asset(asset, &self.collection.key(), self.user.key)?;
+            let multiplier_bps = self.stake_config.multiplier_for(&attributes(asset));
+
+            let (expected, bump) = Pubkey::find_program_address(
+                &[b"stake", config.as_ref(), asset.key.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(
+                stake_account.key(),
+                expected,
+                StakeError::InvalidStakeAccount
+            );
+
+            let signer_seeds: &[&[&[u8]]] =
+                &[&[b"stake", config.as_ref(), asset.key.as_ref(), &[bump]]];
+            self.create_stake_account(stake_account, rent, space as u64, signer_seeds)?;
+
+            let prior_freeze_authority = core.freeze(asset, stake_account, signer_seeds)?;
+
+            let stake = StakeAccount {
+                owner: self.user.key(),
+                mint: asset.key(),
+                staked_at: now,
+                unlock_at: lock_tier.unlock_at(now, self.stake_config.freeze_period),
+                multiplier_bps,
+                lock_tier,
+                prior_freeze_authority,
+                bump,
+            };
+            stake.try_serialize(&mut &mut stake_account.try_borrow_mut_data()?[..])?;
+
+            self.user_account.staked_weight = self
+                .user_account
+                .staked_weight
+                .checked_add(stake.weight())
+                .ok_or(StakeError::Overflow)?;
+            self.stake_config.total_weight = self
+                .stake_config
+                .total_weight
+                .checked_add(stake.weight())
+                .ok_or(StakeError::Overflow)?;
+        }
+
+        self.user_account.amount_staked += count as u8;
+        self.user_account.sync(&self.stake_config)?;
+        Ok(())
+    }
+
+    /// Creates `stake_account` the way Anchor's `init` does: `create_account`
+    /// fails on an address that already holds lamports, so a pre-funded PDA
+    /// is topped up to rent exemption, then allocated and assigned instead.
+    fn create_stake_account(
+        &self,
+        stake_account: &AccountInfo<'info>,
+        rent: u64,
+        space: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let system_program = self.system_program.to_account_info();
+        let lamports = stake_account.lamports();
+        if lamports == 0 {
+            return create_account(
+                CpiContext::new_with_signer(
+                    system_program,
+                    CreateAccount {
+                        from: self.user.to_account_info(),
+                        to: stake_account.clone(),
+                    },
+                    signer_seeds,
+                ),
+                rent,
+                space,
+                &crate::ID,
+            );
+        }
+
+        let top_up = rent.saturating_sub(lamports);
+        if top_up > 0 {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: self.user.to_account_info(),
+                        to: stake_account.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: stake_account.clone(),
+                },
+                signer_seeds,
+            ),
+            space,
+        )?;
+        assign(
+            CpiContext::new_with_signer(
+                system_program,
+                Assign {
+                    account_to_assign: stake_account.clone(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )
+    }
+
+    fn core(&self) -> Core<'info> {
+        Core {
+            core_program: self.core_program.to_account_info(),
+            collection: self.collection.to_account_info(),
+            payer: self.user.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+}
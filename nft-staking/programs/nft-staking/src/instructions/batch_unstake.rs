@@ -0,0 +1,106 @@
+use crate::{asset::Core, error::StakeError, StakeAccount, StakeConfig, UserAccount};
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+#[derive(Accounts)]
+pub struct BatchUnstake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = collection.owner == &CORE_PROGRAM_ID @ StakeError::InvalidCollection,
+        constraint = !collection.data_is_empty() @ StakeError::CollectionNotInitialized
+    )]
+    /// CHECK: Verified by mpl-core
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", stake_config.seed.to_le_bytes().as_ref()],
+        bump = stake_config.bump,
+        constraint = !stake_config.paused @ StakeError::Paused
+    )]
+    pub stake_config: Account<'info, StakeConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), stake_config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: this account is checked by the address constraint
+    pub core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BatchUnstake<'info> {
+    /// Unstakes every `(asset, stake_account)` pair in `pairs`, settling the
+    /// user's rewards once beforehand.
+    pub fn batch_unstake(&mut self, pairs: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            !pairs.is_empty() && pairs.len() % 2 == 0,
+            StakeError::InvalidBatch
+        );
+        let count = pairs.len() / 2;
+
+        let now = Clock::get()?.unix_timestamp;
+        self.stake_config.update_rewards(now)?;
+        self.user_account.settle(&self.stake_config)?;
+
+        let core = self.core();
+        let config = self.stake_config.key();
+        for pair in pairs.chunks(2) {
+            let (asset, stake_account) = (&pair[0], &pair[1]);
+            let stake = Account::<StakeAccount>::try_from(stake_account)?;
+            require_keys_eq!(stake.owner, self.user.key(), StakeError::NotOwner);
+            require_keys_eq!(stake.mint, asset.key(), StakeError::InvalidAsset);
+
+            let bump = [stake.bump];
+            let seeds: &[&[u8]] = &[b"stake", config.as_ref(), asset.key.as_ref(), &bump];
+            let expected = Pubkey::create_program_address(seeds, &crate::ID)
+                .map_err(|_| StakeError::InvalidStakeAccount)?;
+            require_keys_eq!(
+                stake_account.key(),
+                expected,
+                StakeError::InvalidStakeAccount
+            );
+
+            require!(now >= stake.unlock_at, StakeError::FreezePeriodNotPassed);
+
+            core.thaw(asset, stake_account, &[seeds], stake.prior_freeze_authority)?;
+
+            self.user_account.staked_weight = self
+                .user_account
+                .staked_weight
+                .checked_sub(stake.weight())
+                .ok_or(StakeError::Overflow)?;
+            self.stake_config.total_weight = self
+                .stake_config
+                .total_weight
+                .checked_sub(stake.weight())
+                .ok_or(StakeError::Overflow)?;
+            stake.close(self.user.to_account_info())?;
+        }
+
+        self.user_account.amount_staked = self
+            .user_account
+            .amount_staked
+            .checked_sub(count as u8)
+            .ok_or(StakeError::InvalidBatch)?;
+        self.user_account.sync(&self.stake_config)?;
+        Ok(())
+    }
+
+    fn core(&self) -> Core<'info> {
+        Core {
+            core_program: self.core_program.to_account_info(),
+            collection: self.collection.to_account_info(),
+            payer: self.user.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+}
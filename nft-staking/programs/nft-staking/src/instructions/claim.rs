@@ -20,7 +20,7 @@ pub struct Claim<'info> {
 
     #[account(
         mut,
-        seeds = [b"user".as_ref(), user.key().as_ref()],
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
         bump = user_account.bump,
     )]
     pub user_account: Account<'info, UserAccount>,
@@ -33,8 +33,10 @@ pub struct Claim<'info> {
     pub reward_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
-        seeds = [b"config".as_ref()],
+        mut,
+        seeds = [b"config".as_ref(), config.seed.to_le_bytes().as_ref()],
         bump = config.bump,
+        constraint = !config.paused @ StakeError::Paused
     )]
     pub config: Account<'info, StakeConfig>,
 
@@ -47,16 +49,22 @@ pub struct Claim<'info> {
 
 impl<'info> Claim<'info> {
     pub fn claim(&mut self) -> Result<()> {
+        self.config.update_rewards(Clock::get()?.unix_timestamp)?;
+        self.user_account.settle(&self.config)?;
         require!(self.user_account.points > 0, StakeError::NotEnoughPoints);
 
-        let amount = self.user_account.points as u64;
+        let amount = self.user_account.points;
         self.mint_token(amount)?;
         self.user_account.points = 0;
         Ok(())
     }
 
     pub fn mint_token(&mut self, amount: u64) -> Result<()> {
-        let signer_seeds: &[&[&[u8]]] = &[&[b"config", &[self.config.bump]]];
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"config",
+            &self.config.seed.to_le_bytes(),
+            &[self.config.bump],
+        ]];
 
         mint_to_checked(
             CpiContext::new(
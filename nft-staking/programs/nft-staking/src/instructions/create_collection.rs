@@ -5,10 +5,10 @@ use crate::{error::StakeError, CollectionInfo};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateCollectionArgs {
-    name: String,
-    uri: String,
-    nft_name: String,
-    nft_uri: String,
+    pub name: String,
+    pub uri: String,
+    pub nft_name: String,
+    pub nft_uri: String,
 }
 
 #[derive(Accounts)]
@@ -0,0 +1,96 @@
+use crate::{asset::Core, error::StakeError, StakeAccount, StakeConfig, UserAccount};
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
+
+#[derive(Accounts)]
+pub struct EmergencyUnstake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = asset.owner == &CORE_PROGRAM_ID @ StakeError::InvalidAsset,
+        constraint = !asset.data_is_empty() @ StakeError::AssetNotInitialized
+    )]
+    /// CHECK: Verified by mpl-core
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = collection.owner == &CORE_PROGRAM_ID @ StakeError::InvalidCollection,
+        constraint = !collection.data_is_empty() @ StakeError::CollectionNotInitialized
+    )]
+    /// CHECK: Verified by mpl-core
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"stake", stake_config.key().as_ref(), asset.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = &stake_account.owner == user.key @ StakeError::NotOwner
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"config", stake_config.seed.to_le_bytes().as_ref()],
+        bump = stake_config.bump
+    )]
+    pub stake_config: Account<'info, StakeConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), stake_config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+
+    #[account(address = CORE_PROGRAM_ID)]
+    /// CHECK: this account is checked by the address constraint
+    pub core_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EmergencyUnstake<'info> {
+    /// Returns the asset at any time, ignoring the freeze period and any lock
+    /// tier. Rewards this asset accrued since the user's last settlement are
+    /// forfeited; those of the user's other stakes are kept.
+    pub fn emergency_unstake(&mut self) -> Result<()> {
+        // Emissions up to now are split over the weight that earned them
+        // before this asset's weight leaves the pool.
+        self.stake_config
+            .update_rewards(Clock::get()?.unix_timestamp)?;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"stake",
+            &self.stake_config.key().to_bytes(),
+            &self.asset.key().to_bytes(),
+            &[self.stake_account.bump],
+        ]];
+
+        self.core().thaw(
+            &self.asset,
+            &self.stake_account.to_account_info(),
+            signer_seeds,
+            self.stake_account.prior_freeze_authority,
+        )?;
+
+        let weight = self.stake_account.weight();
+        self.user_account.amount_staked = self.user_account.amount_staked.saturating_sub(1);
+        self.stake_config.total_weight = self.stake_config.total_weight.saturating_sub(weight);
+        self.user_account.forfeit(&self.stake_config, weight);
+
+        Ok(())
+    }
+
+    fn core(&self) -> Core<'info> {
+        Core {
+            core_program: self.core_program.to_account_info(),
+            collection: self.collection.to_account_info(),
+            payer: self.user.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+}
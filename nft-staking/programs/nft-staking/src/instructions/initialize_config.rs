@@ -1,8 +1,9 @@
-use crate::StakeConfig;
+use crate::{error::StakeError, CollectionInfo, EmissionSchedule, StakeConfig, MAX_COLLECTIONS};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenInterface};
 
 #[derive(Accounts)]
+#[instruction(seed: u64)]
 pub struct InitializeConfig<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -11,7 +12,7 @@ pub struct InitializeConfig<'info> {
         init,
         payer = admin,
         space = StakeConfig::DISCRIMINATOR.len() + StakeConfig::INIT_SPACE,
-        seeds = [b"config"],
+        seeds = [b"config", seed.to_le_bytes().as_ref()],
         bump
     )]
     pub stake_config: Account<'info, StakeConfig>,
@@ -19,9 +20,9 @@ pub struct InitializeConfig<'info> {
     #[account(
         init,
         payer = admin,
-        mint::authority = admin,
+        mint::authority = stake_config,
         mint::decimals = 6,
-        seeds = [b"reward", stake_config.key().as_ref()],
+        seeds = [b"rewards", stake_config.key().as_ref()],
         bump
     )]
     pub reward_mint: InterfaceAccount<'info, Mint>,
@@ -34,15 +35,45 @@ pub struct InitializeConfig<'info> {
 impl<'info> InitializeConfig<'info> {
     pub fn initialize_config(
         &mut self,
-        points_per_stake: u8,
+        seed: u64,
         max_stake: u8,
         freeze_period: u32,
+        emission: EmissionSchedule,
+        collection_infos: &[AccountInfo<'info>],
         bumps: &InitializeConfigBumps,
     ) -> Result<()> {
+        require!(emission.halving_interval >= 0, StakeError::InvalidEmission);
+        require!(
+            collection_infos.len() <= MAX_COLLECTIONS,
+            StakeError::TooManyCollections
+        );
+
+        let mut collections = Vec::with_capacity(collection_infos.len());
+        for info in collection_infos {
+            require_keys_eq!(*info.owner, crate::ID, StakeError::InvalidCollection);
+            CollectionInfo::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require!(
+                !collections.contains(info.key),
+                StakeError::InvalidCollection
+            );
+            collections.push(info.key());
+        }
+
+        let now = Clock::get()?.unix_timestamp;
         self.stake_config.set_inner(StakeConfig {
-            points_per_stake,
+            seed,
+            admin: self.admin.key(),
             max_stake,
             freeze_period,
+            collections,
+            multipliers: vec![],
+            emission,
+            start_time: now,
+            rewards_emitted: 0,
+            total_weight: 0,
+            acc_reward_per_weight: 0,
+            last_update: now,
+            paused: false,
             reward_bump: bumps.reward_mint,
             bump: bumps.stake_config,
         });
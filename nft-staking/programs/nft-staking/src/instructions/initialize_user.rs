@@ -1,16 +1,22 @@
 use anchor_lang::prelude::*;
 
-use crate::state::UserAccount;
+use crate::state::{StakeConfig, UserAccount};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [b"config", stake_config.seed.to_le_bytes().as_ref()],
+        bump = stake_config.bump
+    )]
+    pub stake_config: Account<'info, StakeConfig>,
+
     #[account(
         init,
         payer = user,
-        seeds = [b"user".as_ref(), user.key().as_ref()],
+        seeds = [b"user".as_ref(), stake_config.key().as_ref(), user.key().as_ref()],
         bump,
         space = UserAccount::DISCRIMINATOR.len() + UserAccount::INIT_SPACE,
     )]
@@ -22,8 +28,11 @@ pub struct Initialize<'info> {
 impl<'info> Initialize<'info> {
     pub fn initialize_user(&mut self, bumps: &InitializeBumps) -> Result<()> {
         self.user_account.set_inner(UserAccount {
+            config: self.stake_config.key(),
             points: 0,
             amount_staked: 0,
+            staked_weight: 0,
+            reward_debt: 0,
             bump: bumps.user_account,
         });
 
@@ -1,15 +1,23 @@
+pub mod batch_stake;
+pub mod batch_unstake;
 pub mod claim;
 pub mod create_collection;
+pub mod emergency_unstake;
 pub mod initialize_config;
 pub mod initialize_user;
 pub mod mint_nft;
 pub mod stake;
 pub mod unstake;
+pub mod update_config;
 
+pub use batch_stake::*;
+pub use batch_unstake::*;
 pub use claim::*;
 pub use create_collection::*;
+pub use emergency_unstake::*;
 pub use initialize_config::*;
 pub use initialize_user::*;
 pub use mint_nft::*;
 pub use stake::*;
 pub use unstake::*;
+pub use update_config::*;
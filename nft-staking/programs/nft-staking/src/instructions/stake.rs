@@ -1,10 +1,10 @@
-use crate::{error::StakeError, StakeAccount, StakeConfig, UserAccount};
-use anchor_lang::prelude::*;
-use mpl_core::{
-    instructions::AddPluginV1CpiBuilder,
-    types::{FreezeDelegate, Plugin},
-    ID as CORE_PROGRAM_ID,
+use crate::{
+    asset::{attributes, verify_asset, Core},
+    error::StakeError,
+    CollectionInfo, LockTier, StakeAccount, StakeConfig, UserAccount,
 };
+use anchor_lang::prelude::*;
+use mpl_core::ID as CORE_PROGRAM_ID;
 
 #[derive(Accounts)]
 pub struct Stake<'info> {
@@ -27,6 +27,13 @@ pub struct Stake<'info> {
     /// CHECK: Verified by mpl-core
     pub collection: UncheckedAccount<'info>,
 
+    #[account(
+        seeds = [b"collection_info", collection.key().as_ref()],
+        bump = collection_info.bump,
+        constraint = stake_config.collections.contains(&collection_info.key()) @ StakeError::CollectionNotAllowed
+    )]
+    pub collection_info: Account<'info, CollectionInfo>,
+
     #[account(
         init,
         payer = user,
@@ -37,14 +44,16 @@ pub struct Stake<'info> {
     pub stake_account: Account<'info, StakeAccount>,
 
     #[account(
-        seeds = [b"config"],
-        bump = stake_config.bump
+        mut,
+        seeds = [b"config", stake_config.seed.to_le_bytes().as_ref()],
+        bump = stake_config.bump,
+        constraint = !stake_config.paused @ StakeError::Paused
     )]
     pub stake_config: Account<'info, StakeConfig>,
 
     #[account(
         mut,
-        seeds = [b"user".as_ref(), user.key().as_ref()],
+        seeds = [b"user".as_ref(), stake_config.key().as_ref(), user.key().as_ref()],
         bump = user_account.bump,
     )]
     pub user_account: Account<'info, UserAccount>,
@@ -57,33 +66,64 @@ pub struct Stake<'info> {
 }
 
 impl<'info> Stake<'info> {
-    pub fn stake(&mut self, bumps: &StakeBumps) -> Result<()> {
+    pub fn stake(&mut self, lock_tier: LockTier, bumps: &StakeBumps) -> Result<()> {
         require!(
             self.user_account.amount_staked < self.stake_config.max_stake,
             StakeError::MaxStakeReached
         );
+        verify_asset(&self.asset, &self.collection.key(), self.user.key)?;
+        let multiplier_bps = self.stake_config.multiplier_for(&attributes(&self.asset));
 
-        AddPluginV1CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .collection(Some(&self.collection.to_account_info()))
-            .payer(&self.user.to_account_info())
-            .authority(None)
-            .system_program(&self.system_program.to_account_info())
-            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: true }))
-            .init_authority(mpl_core::types::PluginAuthority::Address {
-                address: self.stake_account.key(),
-            })
-            .invoke()?;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"stake",
+            &self.stake_config.key().to_bytes(),
+            &self.asset.key().to_bytes(),
+            &[bumps.stake_account],
+        ]];
 
-        self.user_account.amount_staked = self.user_account.amount_staked.saturating_add(1);
+        let prior_freeze_authority = self.core().freeze(
+            &self.asset,
+            &self.stake_account.to_account_info(),
+            signer_seeds,
+        )?;
 
+        let now = Clock::get()?.unix_timestamp;
         self.stake_account.set_inner(StakeAccount {
             owner: self.user.key(),
             mint: self.asset.key(),
-            staked_at: Clock::get()?.unix_timestamp,
+            staked_at: now,
+            unlock_at: lock_tier.unlock_at(now, self.stake_config.freeze_period),
+            multiplier_bps,
+            lock_tier,
+            prior_freeze_authority,
             bump: bumps.stake_account,
         });
+        let weight = self.stake_account.weight();
+
+        self.stake_config.update_rewards(now)?;
+        self.user_account.settle(&self.stake_config)?;
+        self.user_account.amount_staked = self.user_account.amount_staked.saturating_add(1);
+        self.user_account.staked_weight = self
+            .user_account
+            .staked_weight
+            .checked_add(weight)
+            .ok_or(StakeError::Overflow)?;
+        self.stake_config.total_weight = self
+            .stake_config
+            .total_weight
+            .checked_add(weight)
+            .ok_or(StakeError::Overflow)?;
+        self.user_account.sync(&self.stake_config)?;
 
         Ok(())
     }
+
+    fn core(&self) -> Core<'info> {
+        Core {
+            core_program: self.core_program.to_account_info(),
+            collection: self.collection.to_account_info(),
+            payer: self.user.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
 }
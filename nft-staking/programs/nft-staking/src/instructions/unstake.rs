@@ -1,10 +1,6 @@
-use crate::{error::StakeError, StakeAccount, StakeConfig, UserAccount};
+use crate::{asset::Core, error::StakeError, StakeAccount, StakeConfig, UserAccount};
 use anchor_lang::prelude::*;
-use mpl_core::{
-    instructions::{RemovePluginV1CpiBuilder, UpdatePluginV1CpiBuilder},
-    types::{FreezeDelegate, Plugin, PluginType},
-    ID as CORE_PROGRAM_ID,
-};
+use mpl_core::ID as CORE_PROGRAM_ID;
 
 #[derive(Accounts)]
 pub struct Unstake<'info> {
@@ -32,19 +28,21 @@ pub struct Unstake<'info> {
         close = user,
         seeds = [b"stake", stake_config.key().as_ref(), asset.key().as_ref()],
         bump = stake_account.bump,
-        constraint = &stake_account.owner == user.key @ StakeError::NotOwner 
+        constraint = &stake_account.owner == user.key @ StakeError::NotOwner
     )]
     pub stake_account: Account<'info, StakeAccount>,
 
     #[account(
-        seeds = [b"config"],
-        bump = stake_config.bump
+        mut,
+        seeds = [b"config", stake_config.seed.to_le_bytes().as_ref()],
+        bump = stake_config.bump,
+        constraint = !stake_config.paused @ StakeError::Paused
     )]
     pub stake_config: Account<'info, StakeConfig>,
 
     #[account(
         mut,
-        seeds = [b"user".as_ref(), user.key().as_ref()],
+        seeds = [b"user".as_ref(), stake_config.key().as_ref(), user.key().as_ref()],
         bump = user_account.bump,
     )]
     pub user_account: Account<'info, UserAccount>,
@@ -58,15 +56,13 @@ pub struct Unstake<'info> {
 
 impl<'info> Unstake<'info> {
     pub fn unstake(&mut self) -> Result<()> {
-        let time_elapsed =
-            ((Clock::get()?.unix_timestamp - self.stake_account.staked_at) / 86_400) as u32;
-
+        let now = Clock::get()?.unix_timestamp;
         require!(
-            time_elapsed >= self.stake_config.freeze_period,
+            now >= self.stake_account.unlock_at,
             StakeError::FreezePeriodNotPassed
         );
-        let points_earned = time_elapsed * self.stake_config.points_per_stake as u32;
-        self.user_account.points = self.user_account.points.saturating_add(points_earned);
+        self.stake_config.update_rewards(now)?;
+        self.user_account.settle(&self.stake_config)?;
 
         let signer_seeds: &[&[&[u8]]] = &[&[
             b"stake",
@@ -75,26 +71,36 @@ impl<'info> Unstake<'info> {
             &[self.stake_account.bump],
         ]];
 
-        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .collection(Some(&self.collection.to_account_info()))
-            .payer(&self.user.to_account_info())
-            .authority(Some(&self.stake_account.to_account_info()))
-            .system_program(&self.system_program.to_account_info())
-            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
-            .invoke_signed(signer_seeds)?;
-
-        RemovePluginV1CpiBuilder::new(&self.core_program.to_account_info())
-            .asset(&self.asset.to_account_info())
-            .collection(Some(&self.collection.to_account_info()))
-            .payer(&self.user.to_account_info())
-            .authority(None)
-            .system_program(&self.system_program.to_account_info())
-            .plugin_type(PluginType::FreezeDelegate)
-            .invoke()?;
+        self.core().thaw(
+            &self.asset,
+            &self.stake_account.to_account_info(),
+            signer_seeds,
+            self.stake_account.prior_freeze_authority,
+        )?;
 
         self.user_account.amount_staked = self.user_account.amount_staked.saturating_sub(1);
+        let weight = self.stake_account.weight();
+        self.user_account.staked_weight = self
+            .user_account
+            .staked_weight
+            .checked_sub(weight)
+            .ok_or(StakeError::Overflow)?;
+        self.stake_config.total_weight = self
+            .stake_config
+            .total_weight
+            .checked_sub(weight)
+            .ok_or(StakeError::Overflow)?;
+        self.user_account.sync(&self.stake_config)?;
 
         Ok(())
     }
+
+    fn core(&self) -> Core<'info> {
+        Core {
+            core_program: self.core_program.to_account_info(),
+            collection: self.collection.to_account_info(),
+            payer: self.user.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
 }
@@ -0,0 +1,60 @@
+use crate::{error::StakeError, AttributeMultiplier, StakeConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ StakeError::NotAdmin,
+        seeds = [b"config", stake_config.seed.to_le_bytes().as_ref()],
+        bump = stake_config.bump
+    )]
+    pub stake_config: Account<'info, StakeConfig>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    /// Rewards emitted so far are accumulated at the old rate before
+    /// `reward_per_second` changes. The budget is fixed at initialization, and
+    /// `freeze_period` only applies to assets staked from now on.
+    pub fn update_config(
+        &mut self,
+        reward_per_second: u64,
+        max_stake: u8,
+        freeze_period: u32,
+    ) -> Result<()> {
+        self.stake_config
+            .update_rewards(Clock::get()?.unix_timestamp)?;
+        self.stake_config.emission.reward_per_second = reward_per_second;
+        self.stake_config.max_stake = max_stake;
+        self.stake_config.freeze_period = freeze_period;
+        Ok(())
+    }
+
+    /// Rewards are accumulated before the table changes. Staked assets keep the
+    /// multiplier recorded when they were staked; the new table applies to
+    /// assets staked from now on.
+    pub fn update_multipliers(&mut self, multipliers: Vec<AttributeMultiplier>) -> Result<()> {
+        self.stake_config
+            .update_rewards(Clock::get()?.unix_timestamp)?;
+        self.stake_config.set_multipliers(multipliers)
+    }
+
+    /// Nothing is emitted while paused.
+    pub fn pause(&mut self) -> Result<()> {
+        require!(!self.stake_config.paused, StakeError::Paused);
+        self.stake_config
+            .update_rewards(Clock::get()?.unix_timestamp)?;
+        self.stake_config.paused = true;
+        Ok(())
+    }
+
+    pub fn unpause(&mut self) -> Result<()> {
+        require!(self.stake_config.paused, StakeError::NotPaused);
+        self.stake_config
+            .update_rewards(Clock::get()?.unix_timestamp)?;
+        self.stake_config.paused = false;
+        Ok(())
+    }
+}
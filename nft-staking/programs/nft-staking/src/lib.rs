@@ -1,3 +1,4 @@
+pub mod asset;
 pub mod constants;
 pub mod error;
 pub mod instructions;
@@ -15,14 +16,48 @@ declare_id!("4r5xfzNSHZ2uBY2Tctqwt3S6Ug3DAamKcsT2rvU8NSPc");
 pub mod nft_staking {
     use super::*;
 
-    pub fn initialize_config(
-        ctx: Context<InitializeConfig>,
-        points_per_stake: u8,
+    pub fn initialize_config<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializeConfig<'info>>,
+        seed: u64,
+        max_stake: u8,
+        freeze_period: u32,
+        emission: EmissionSchedule,
+        multipliers: Vec<AttributeMultiplier>,
+    ) -> Result<()> {
+        ctx.accounts.initialize_config(
+            seed,
+            max_stake,
+            freeze_period,
+            emission,
+            ctx.remaining_accounts,
+            &ctx.bumps,
+        )?;
+        ctx.accounts.stake_config.set_multipliers(multipliers)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        reward_per_second: u64,
         max_stake: u8,
         freeze_period: u32,
     ) -> Result<()> {
         ctx.accounts
-            .initialize_config(points_per_stake, max_stake, freeze_period, &ctx.bumps)
+            .update_config(reward_per_second, max_stake, freeze_period)
+    }
+
+    pub fn update_multipliers(
+        ctx: Context<UpdateConfig>,
+        multipliers: Vec<AttributeMultiplier>,
+    ) -> Result<()> {
+        ctx.accounts.update_multipliers(multipliers)
+    }
+
+    pub fn pause(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.pause()
+    }
+
+    pub fn unpause(ctx: Context<UpdateConfig>) -> Result<()> {
+        ctx.accounts.unpause()
     }
 
     pub fn initialize_user(ctx: Context<Initialize>) -> Result<()> {
@@ -40,14 +75,31 @@ pub mod nft_staking {
         ctx.accounts.mint_nft()
     }
 
-    pub fn stake(ctx: Context<Stake>) -> Result<()> {
-        ctx.accounts.stake(&ctx.bumps)
+    pub fn stake(ctx: Context<Stake>, lock_tier: LockTier) -> Result<()> {
+        ctx.accounts.stake(lock_tier, &ctx.bumps)
     }
 
     pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
         ctx.accounts.unstake()
     }
 
+    pub fn batch_stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchStake<'info>>,
+        lock_tier: LockTier,
+    ) -> Result<()> {
+        ctx.accounts.batch_stake(lock_tier, ctx.remaining_accounts)
+    }
+
+    pub fn batch_unstake<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchUnstake<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.batch_unstake(ctx.remaining_accounts)
+    }
+
+    pub fn emergency_unstake(ctx: Context<EmergencyUnstake>) -> Result<()> {
+        ctx.accounts.emergency_unstake()
+    }
+
     pub fn claim(ctx: Context<Claim>) -> Result<()> {
         ctx.accounts.claim()
     }
@@ -1,4 +1,6 @@
+use crate::{BASE_MULTIPLIER_BPS, SECONDS_PER_DAY};
 use anchor_lang::prelude::*;
+use mpl_core::types::PluginAuthority;
 
 #[account]
 #[derive(InitSpace)]
@@ -6,5 +8,116 @@ pub struct StakeAccount {
     pub owner: Pubkey,
     pub mint: Pubkey,
     pub staked_at: i64,
+    pub unlock_at: i64, // fixed at stake time from freeze_period and lock_tier
+    pub multiplier_bps: u16,
+    pub lock_tier: LockTier,
+    pub prior_freeze_authority: Option<FreezeAuthority>, // existing FreezeDelegate to restore on unstake
     pub bump: u8,
 }
+
+impl StakeAccount {
+    /// Share of emissions this stake earns: the attribute multiplier boosted
+    /// by the lock tier.
+    pub fn weight(&self) -> u64 {
+        self.multiplier_bps as u64 * self.lock_tier.multiplier_bps() as u64
+            / BASE_MULTIPLIER_BPS as u64
+    }
+}
+
+/// Authority of a FreezeDelegate the asset already carried when it was
+/// staked. Mirrors mpl-core's `PluginAuthority`, which has no `InitSpace`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum FreezeAuthority {
+    None,
+    Owner,
+    UpdateAuthority,
+    Address { address: Pubkey },
+}
+
+impl From<PluginAuthority> for FreezeAuthority {
+    fn from(authority: PluginAuthority) -> Self {
+        match authority {
+            PluginAuthority::None => FreezeAuthority::None,
+            PluginAuthority::Owner => FreezeAuthority::Owner,
+            PluginAuthority::UpdateAuthority => FreezeAuthority::UpdateAuthority,
+            PluginAuthority::Address { address } => FreezeAuthority::Address { address },
+        }
+    }
+}
+
+impl From<FreezeAuthority> for PluginAuthority {
+    fn from(authority: FreezeAuthority) -> Self {
+        match authority {
+            FreezeAuthority::None => PluginAuthority::None,
+            FreezeAuthority::Owner => PluginAuthority::Owner,
+            FreezeAuthority::UpdateAuthority => PluginAuthority::UpdateAuthority,
+            FreezeAuthority::Address { address } => PluginAuthority::Address { address },
+        }
+    }
+}
+
+/// Lock chosen at stake time. Longer locks earn a larger reward multiplier.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum LockTier {
+    Flexible,
+    Week,
+    Month,
+    Quarter,
+}
+
+impl LockTier {
+    pub fn days(&self) -> u32 {
+        match self {
+            LockTier::Flexible => 0,
+            LockTier::Week => 7,
+            LockTier::Month => 30,
+            LockTier::Quarter => 90,
+        }
+    }
+
+    /// When an asset staked at `staked_at` under this tier can be unstaked.
+    /// The tier's days come on top of the config's `freeze_period`, so every
+    /// boosted tier locks longer than a flexible stake. Later changes to
+    /// `freeze_period` do not move it.
+    pub fn unlock_at(&self, staked_at: i64, freeze_period: u32) -> i64 {
+        staked_at + (freeze_period as i64 + self.days() as i64) * SECONDS_PER_DAY
+    }
+
+    pub fn multiplier_bps(&self) -> u16 {
+        match self {
+            LockTier::Flexible => BASE_MULTIPLIER_BPS,
+            LockTier::Week => 11_000,
+            LockTier::Month => 12_500,
+            LockTier::Quarter => 15_000,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_tiers_add_to_the_freeze_period() {
+        assert_eq!(LockTier::Flexible.unlock_at(100, 0), 100);
+        assert_eq!(
+            LockTier::Flexible.unlock_at(100, 30),
+            100 + 30 * SECONDS_PER_DAY
+        );
+        assert_eq!(
+            LockTier::Week.unlock_at(100, 30),
+            100 + 37 * SECONDS_PER_DAY
+        );
+
+        // a boosted tier always locks longer than a flexible stake
+        for freeze_period in [0, 7, 30, 90, 365] {
+            let flexible = LockTier::Flexible.unlock_at(0, freeze_period);
+            for tier in [LockTier::Week, LockTier::Month, LockTier::Quarter] {
+                assert_eq!(
+                    tier.unlock_at(0, freeze_period) - flexible,
+                    tier.days() as i64 * SECONDS_PER_DAY
+                );
+            }
+        }
+    }
+}
@@ -1,11 +1,285 @@
 use anchor_lang::prelude::*;
+use mpl_core::types::Attribute;
+
+use crate::{error::StakeError, ACC_PRECISION, BASE_MULTIPLIER_BPS, MAX_MULTIPLIERS};
 
 #[account]
 #[derive(InitSpace)]
 pub struct StakeConfig {
-    pub points_per_stake: u8,
+    pub seed: u64,
+    pub admin: Pubkey,
     pub max_stake: u8,
     pub freeze_period: u32,
+    #[max_len(8)]
+    pub collections: Vec<Pubkey>, // allowlisted CollectionInfo accounts
+    #[max_len(8)]
+    pub multipliers: Vec<AttributeMultiplier>, // reward boosts by asset attribute
+    pub emission: EmissionSchedule,
+    pub start_time: i64,             // halving epochs count from here
+    pub rewards_emitted: u64,        // never exceeds emission.reward_budget
+    pub total_weight: u64,           // sum of staked multipliers, in bps
+    pub acc_reward_per_weight: u128, // scaled by ACC_PRECISION
+    pub last_update: i64,
+    pub paused: bool,
     pub reward_bump: u8,
     pub bump: u8,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct EmissionSchedule {
+    pub reward_per_second: u64, // shared across all staked weight
+    pub reward_budget: u64,     // total tokens ever emitted
+    pub halving_interval: i64,  // seconds per halving epoch, 0 = no halving
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct AttributeMultiplier {
+    #[max_len(16)]
+    pub key: String,
+
+    #[max_len(64)]
+    pub value: String,
+
+    pub multiplier_bps: u16, // 10_000 = 1x
+}
+
+impl StakeConfig {
+    pub fn set_multipliers(&mut self, multipliers: Vec<AttributeMultiplier>) -> Result<()> {
+        require!(
+            multipliers.len() <= MAX_MULTIPLIERS,
+            StakeError::InvalidMultiplier
+        );
+        for multiplier in &multipliers {
+            require!(
+                multiplier.multiplier_bps >= BASE_MULTIPLIER_BPS
+                    && multiplier.key.len() <= 16
+                    && multiplier.value.len() <= 64,
+                StakeError::InvalidMultiplier
+            );
+        }
+
+        self.multipliers = multipliers;
+        Ok(())
+    }
+
+    /// Highest multiplier matching any of `attributes`, or 1x if none match.
+    pub fn multiplier_for(&self, attributes: &[Attribute]) -> u16 {
+        self.multipliers
+            .iter()
+            .filter(|m| {
+                attributes
+                    .iter()
+                    .any(|a| a.key == m.key && a.value == m.value)
+            })
+            .map(|m| m.multiplier_bps)
+            .max()
+            .unwrap_or(BASE_MULTIPLIER_BPS)
+    }
+
+    /// Advances the reward accumulator to `now`.
+    ///
+    /// Nothing is emitted while paused or while nothing is staked, so that
+    /// part of the budget stays available for later stakers.
+    pub fn update_rewards(&mut self, now: i64) -> Result<()> {
+        if now <= self.last_update {
+            return Ok(());
+        }
+        if self.paused || self.total_weight == 0 {
+            self.last_update = now;
+            return Ok(());
+        }
+
+        let remaining = self
+            .emission
+            .reward_budget
+            .checked_sub(self.rewards_emitted)
+            .ok_or(StakeError::Overflow)?;
+        let reward = self
+            .emitted_between(self.last_update, now)
+            .min(remaining as u128);
+        let per_weight = reward
+            .checked_mul(ACC_PRECISION)
+            .ok_or(StakeError::Overflow)?
+            / self.total_weight as u128;
+
+        self.acc_reward_per_weight = self
+            .acc_reward_per_weight
+            .checked_add(per_weight)
+            .ok_or(StakeError::Overflow)?;
+        self.rewards_emitted = self
+            .rewards_emitted
+            .checked_add(u64::try_from(reward).map_err(|_| StakeError::Overflow)?)
+            .ok_or(StakeError::Overflow)?;
+        self.last_update = now;
+        Ok(())
+    }
+
+    /// Rewards scheduled between `from` and `to`, halving the rate every
+    /// `halving_interval` seconds after `start_time`.
+    fn emitted_between(&self, from: i64, to: i64) -> u128 {
+        let rate = self.emission.reward_per_second as u128;
+        let interval = self.emission.halving_interval;
+        if interval == 0 {
+            return rate * (to - from) as u128;
+        }
+
+        let mut total = 0;
+        let mut t = from;
+        while t < to {
+            let epoch = (t - self.start_time) / interval;
+            if epoch >= u64::BITS as i64 {
+                break;
+            }
+            let end = to.min(self.start_time + (epoch + 1) * interval);
+            total += (rate >> epoch) * (end - t) as u128;
+            t = end;
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UserAccount;
+
+    fn config(reward_budget: u64, halving_interval: i64) -> StakeConfig {
+        StakeConfig {
+            seed: 1,
+            admin: Pubkey::default(),
+            max_stake: 5,
+            freeze_period: 0,
+            collections: vec![],
+            multipliers: vec![],
+            emission: EmissionSchedule {
+                reward_per_second: 1_000,
+                reward_budget,
+                halving_interval,
+            },
+            start_time: 0,
+            rewards_emitted: 0,
+            total_weight: 0,
+            acc_reward_per_weight: 0,
+            last_update: 0,
+            paused: false,
+            reward_bump: 255,
+            bump: 255,
+        }
+    }
+
+    fn user() -> UserAccount {
+        UserAccount {
+            config: Pubkey::default(),
+            points: 0,
+            amount_staked: 0,
+            staked_weight: 0,
+            reward_debt: 0,
+            bump: 255,
+        }
+    }
+
+    /// Adds `weight` for `user` at `now`, as `stake` does.
+    fn stake(config: &mut StakeConfig, user: &mut UserAccount, weight: u64, now: i64) {
+        config.update_rewards(now).unwrap();
+        user.settle(config).unwrap();
+        user.staked_weight += weight;
+        config.total_weight += weight;
+        user.sync(config).unwrap();
+    }
+
+    #[test]
+    fn halves_at_epoch_boundaries() {
+        let config = config(u64::MAX, 100);
+        assert_eq!(config.emitted_between(0, 100), 100_000);
+        assert_eq!(config.emitted_between(100, 200), 50_000);
+        assert_eq!(config.emitted_between(0, 200), 150_000);
+        assert_eq!(config.emitted_between(50, 150), 75_000);
+        assert_eq!(config.emitted_between(99, 101), 1_500);
+        assert_eq!(config.emitted_between(100, 100), 0);
+    }
+
+    #[test]
+    fn stops_once_the_rate_halves_to_zero() {
+        let config = config(u64::MAX, 100);
+        // 1000, 500, 250, 125, 62, 31, 15, 7, 3, 1 per second
+        assert_eq!(config.emitted_between(0, 1_000), 199_400);
+        assert_eq!(config.emitted_between(0, i64::MAX), 199_400);
+    }
+
+    #[test]
+    fn emits_nothing_past_the_budget() {
+        let mut config = config(150_000, 0);
+        config.total_weight = 10_000;
+
+        config.update_rewards(100).unwrap();
+        assert_eq!(config.rewards_emitted, 100_000);
+
+        config.update_rewards(300).unwrap();
+        assert_eq!(config.rewards_emitted, 150_000);
+        let acc = config.acc_reward_per_weight;
+
+        config.update_rewards(400).unwrap();
+        assert_eq!(config.rewards_emitted, 150_000);
+        assert_eq!(config.acc_reward_per_weight, acc);
+        assert_eq!(config.last_update, 400);
+    }
+
+    #[test]
+    fn rejects_emissions_beyond_the_budget() {
+        let mut config = config(100, 0);
+        config.total_weight = 10_000;
+        config.rewards_emitted = 101;
+        assert!(config.update_rewards(1).is_err());
+    }
+
+    #[test]
+    fn skips_time_while_paused_or_empty() {
+        let mut config = config(u64::MAX, 0);
+        config.update_rewards(100).unwrap();
+        assert_eq!(config.rewards_emitted, 0);
+
+        config.total_weight = 10_000;
+        config.paused = true;
+        config.update_rewards(200).unwrap();
+        assert_eq!(config.rewards_emitted, 0);
+        assert_eq!(config.last_update, 200);
+    }
+
+    #[test]
+    fn splits_emissions_by_weight_over_time() {
+        let mut config = config(u64::MAX, 0);
+        let (mut first, mut second) = (user(), user());
+
+        stake(&mut config, &mut first, 10_000, 0);
+        stake(&mut config, &mut second, 30_000, 100);
+
+        config.update_rewards(200).unwrap();
+        first.settle(&config).unwrap();
+        second.settle(&config).unwrap();
+
+        // alone for 100s, then a quarter of the next 100s
+        assert_eq!(first.points, 125_000);
+        assert_eq!(second.points, 75_000);
+        assert_eq!(first.points + second.points, config.rewards_emitted);
+
+        // settling again pays nothing new
+        first.settle(&config).unwrap();
+        assert_eq!(first.points, 125_000);
+    }
+
+    #[test]
+    fn forfeit_keeps_the_remaining_weight_share() {
+        let mut config = config(u64::MAX, 0);
+        let mut user = user();
+        stake(&mut config, &mut user, 10_000, 0);
+        stake(&mut config, &mut user, 30_000, 0);
+
+        config.update_rewards(100).unwrap();
+        user.forfeit(&config, 10_000);
+        assert_eq!(user.staked_weight, 30_000);
+        assert_eq!(user.points, 75_000);
+
+        user.settle(&config).unwrap();
+        assert_eq!(user.points, 75_000);
+    }
+}
@@ -1,9 +1,67 @@
 use anchor_lang::prelude::*;
 
+use crate::{error::StakeError, StakeConfig, ACC_PRECISION};
+
 #[account]
 #[derive(InitSpace)]
 pub struct UserAccount {
-    pub points: u32,
+    pub config: Pubkey,
+    pub points: u64, // settled rewards awaiting claim
     pub amount_staked: u8,
+    pub staked_weight: u64, // sum of staked multipliers, in bps
+    pub reward_debt: u128,  // accumulator share already settled
     pub bump: u8,
 }
+
+impl UserAccount {
+    /// Banks the rewards earned by `staked_weight` since the last settlement.
+    /// Call after `StakeConfig::update_rewards`.
+    pub fn settle(&mut self, config: &StakeConfig) -> Result<()> {
+        let earned = self.accumulated(config)?.saturating_sub(self.reward_debt);
+        self.points = self
+            .points
+            .checked_add(u64::try_from(earned).map_err(|_| StakeError::Overflow)?)
+            .ok_or(StakeError::Overflow)?;
+        self.reward_debt += earned;
+        Ok(())
+    }
+
+    /// Marks everything accumulated so far as settled. Call after changing
+    /// `staked_weight`.
+    pub fn sync(&mut self, config: &StakeConfig) -> Result<()> {
+        self.reward_debt = self.accumulated(config)?;
+        Ok(())
+    }
+
+    /// Removes `weight` from `staked_weight`, dropping only the unsettled
+    /// rewards it earned. The remaining weight's share is banked into `points`;
+    /// every weight change settles first, so unsettled rewards accrued at the
+    /// current weight and split pro rata. Unlike `settle` this cannot fail, so
+    /// users can always leave.
+    pub fn forfeit(&mut self, config: &StakeConfig, weight: u64) {
+        let remaining = self.staked_weight.saturating_sub(weight);
+        let pending = self
+            .accumulated_saturating(config)
+            .saturating_sub(self.reward_debt);
+        if self.staked_weight > 0 {
+            let kept = pending.saturating_mul(remaining as u128) / self.staked_weight as u128;
+            self.points = self
+                .points
+                .saturating_add(u64::try_from(kept).unwrap_or(u64::MAX));
+        }
+
+        self.staked_weight = remaining;
+        self.reward_debt = self.accumulated_saturating(config);
+    }
+
+    fn accumulated_saturating(&self, config: &StakeConfig) -> u128 {
+        (self.staked_weight as u128).saturating_mul(config.acc_reward_per_weight) / ACC_PRECISION
+    }
+
+    fn accumulated(&self, config: &StakeConfig) -> Result<u128> {
+        Ok((self.staked_weight as u128)
+            .checked_mul(config.acc_reward_per_weight)
+            .ok_or(StakeError::Overflow)?
+            / ACC_PRECISION)
+    }
+}
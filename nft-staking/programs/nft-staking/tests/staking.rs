@@ -0,0 +1,962 @@
+//! End-to-end staking flow run in an in-process bank against the program built
+//! by `anchor build` and the mpl-core dump in `tests/fixtures`, which also seeds
+//! the `anchor test` validator. Anchor's CPI helpers only run on the SBF target,
+//! so a plain `cargo test` skips the suite. `anchor test` and CI
+//! (`.github/workflows/nft-staking.yml`) build the program first and run it:
+//!
+//! ```sh
+//! anchor run fixtures  # once, to dump mpl-core from mainnet
+//! anchor build && cargo test -p nft-staking --test staking -- --ignored
+//! ```
+
+use anchor_lang::{
+    prelude::{Clock, Pubkey},
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program_pack::Pack,
+        system_instruction,
+    },
+    system_program, AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::spl_token::{self, state::Account as TokenAccount},
+};
+use mpl_core::{
+    instructions::AddPluginV1Builder,
+    types::{FreezeDelegate, Plugin},
+    Asset, AuthorityType,
+};
+use nft_staking::{
+    AttributeMultiplier, CreateCollectionArgs, EmissionSchedule, FreezeAuthority, LockTier,
+    StakeAccount, StakeConfig, UserAccount, SECONDS_PER_DAY,
+};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    bpf_loader,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+const REWARD_PER_SECOND: u64 = 1_000;
+const DAY_REWARD: u64 = REWARD_PER_SECOND * SECONDS_PER_DAY as u64;
+const MAX_STAKE: u8 = 5;
+const FREEZE_PERIOD: u32 = 1;
+const PROGRAM_SO: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../target/deploy/nft_staking.so"
+);
+const MPL_CORE_SO: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../tests/fixtures/mpl_core.so"
+);
+
+struct Staking {
+    ctx: ProgramTestContext,
+    config: Pubkey,
+    reward_mint: Pubkey,
+    collection: Pubkey,
+    collection_info: Pubkey,
+}
+
+impl Staking {
+    async fn new() -> Self {
+        let mut program_test = ProgramTest::default();
+        for (program_id, path) in [(nft_staking::ID, PROGRAM_SO), (mpl_core::ID, MPL_CORE_SO)] {
+            let elf = std::fs::read(path).unwrap_or_else(|_| panic!("missing {path}"));
+            program_test.add_account(
+                program_id,
+                Account {
+                    lamports: 1_000_000_000,
+                    data: elf,
+                    owner: bpf_loader::ID,
+                    executable: true,
+                    rent_epoch: 0,
+                },
+            );
+        }
+        let ctx = program_test.start_with_context().await;
+
+        let mut staking = Self {
+            config: Pubkey::default(),
+            reward_mint: Pubkey::default(),
+            collection: Pubkey::default(),
+            collection_info: Pubkey::default(),
+            ctx,
+        };
+        staking.open_pool(1, schedule(), vec![]).await;
+        staking
+    }
+
+    /// Creates a collection and a config staking it under `seed`, and points
+    /// every helper at the new pool.
+    async fn open_pool(
+        &mut self,
+        seed: u64,
+        emission: EmissionSchedule,
+        multipliers: Vec<AttributeMultiplier>,
+    ) {
+        (self.collection, self.collection_info) = self.create_collection().await;
+        self.config = pda(&[b"config", &seed.to_le_bytes()]);
+        self.reward_mint = pda(&[b"rewards", self.config.as_ref()]);
+
+        let mut ix = instruction(
+            nft_staking::accounts::InitializeConfig {
+                admin: self.ctx.payer.pubkey(),
+                stake_config: self.config,
+                reward_mint: self.reward_mint,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            nft_staking::instruction::InitializeConfig {
+                seed,
+                max_stake: MAX_STAKE,
+                freeze_period: FREEZE_PERIOD,
+                emission,
+                multipliers,
+            },
+        );
+        ix.accounts
+            .push(AccountMeta::new_readonly(self.collection_info, false));
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    async fn send(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+        let mut all_signers = vec![&self.ctx.payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.ctx.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    /// Creates a collection through the program, returning it and its
+    /// `CollectionInfo`.
+    async fn create_collection(&mut self) -> (Pubkey, Pubkey) {
+        let collection = Keypair::new();
+        let collection_info = pda(&[b"collection_info", collection.pubkey().as_ref()]);
+        let ix = instruction(
+            nft_staking::accounts::CreateCollection {
+                authority: self.ctx.payer.pubkey(),
+                collection: collection.pubkey(),
+                collection_info,
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            nft_staking::instruction::CreateCollection {
+                args: CreateCollectionArgs {
+                    name: "Staking Collection".to_string(),
+                    uri: "https://example.com/collection.json".to_string(),
+                    nft_name: "Staked NFT".to_string(),
+                    nft_uri: "https://example.com/nft.json".to_string(),
+                },
+            },
+        );
+        self.send(&[ix], &[&collection]).await.unwrap();
+        (collection.pubkey(), collection_info)
+    }
+
+    /// A funded wallet with an initialized user account.
+    async fn user(&mut self) -> Keypair {
+        let user = Keypair::new();
+        let fund =
+            system_instruction::transfer(&self.ctx.payer.pubkey(), &user.pubkey(), 1_000_000_000);
+        self.send(&[fund], &[]).await.unwrap();
+        self.join(&user).await;
+        user
+    }
+
+    /// Initializes `user`'s account in the current pool.
+    async fn join(&mut self, user: &Keypair) {
+        let init = instruction(
+            nft_staking::accounts::Initialize {
+                user: user.pubkey(),
+                stake_config: self.config,
+                user_account: self.user_pda(&user.pubkey()),
+                system_program: system_program::ID,
+            },
+            nft_staking::instruction::InitializeUser {},
+        );
+        self.send(&[init], &[user]).await.unwrap();
+    }
+
+    /// Mints an asset of the staking collection to `owner`.
+    async fn mint(&mut self, owner: &Keypair) -> Pubkey {
+        let (collection, collection_info) = (self.collection, self.collection_info);
+        self.mint_from(owner, collection, collection_info).await
+    }
+
+    async fn mint_from(
+        &mut self,
+        owner: &Keypair,
+        collection: Pubkey,
+        collection_info: Pubkey,
+    ) -> Pubkey {
+        let asset = Keypair::new();
+        let ix = instruction(
+            nft_staking::accounts::MintNft {
+                minter: owner.pubkey(),
+                asset: asset.pubkey(),
+                collection,
+                collection_info,
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            nft_staking::instruction::MintNft {},
+        );
+        self.send(&[ix], &[owner, &asset]).await.unwrap();
+        asset.pubkey()
+    }
+
+    /// Adds a FreezeDelegate owned by `owner`, as marketplaces and other
+    /// programs do before the asset reaches the staking program.
+    async fn add_freeze_delegate(&mut self, owner: &Keypair, asset: Pubkey, frozen: bool) {
+        let ix = AddPluginV1Builder::new()
+            .asset(asset)
+            .collection(Some(self.collection))
+            .payer(owner.pubkey())
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen }))
+            .instruction();
+        self.send(&[ix], &[owner]).await.unwrap();
+    }
+
+    async fn stake(&mut self, user: &Keypair, asset: Pubkey) -> Result<(), BanksClientError> {
+        self.stake_locked(user, asset, LockTier::Flexible).await
+    }
+
+    async fn stake_locked(
+        &mut self,
+        user: &Keypair,
+        asset: Pubkey,
+        lock_tier: LockTier,
+    ) -> Result<(), BanksClientError> {
+        let (collection, collection_info) = (self.collection, self.collection_info);
+        self.stake_in(user, asset, collection, collection_info, lock_tier)
+            .await
+    }
+
+    async fn stake_in(
+        &mut self,
+        user: &Keypair,
+        asset: Pubkey,
+        collection: Pubkey,
+        collection_info: Pubkey,
+        lock_tier: LockTier,
+    ) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            nft_staking::accounts::Stake {
+                user: user.pubkey(),
+                asset,
+                collection,
+                collection_info,
+                stake_account: self.stake_pda(&asset),
+                stake_config: self.config,
+                user_account: self.user_pda(&user.pubkey()),
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            nft_staking::instruction::Stake { lock_tier },
+        );
+        self.send(&[ix], &[user]).await
+    }
+
+    async fn unstake(&mut self, user: &Keypair, asset: Pubkey) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            nft_staking::accounts::Unstake {
+                user: user.pubkey(),
+                asset,
+                collection: self.collection,
+                stake_account: self.stake_pda(&asset),
+                stake_config: self.config,
+                user_account: self.user_pda(&user.pubkey()),
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            nft_staking::instruction::Unstake {},
+        );
+        self.send(&[ix], &[user]).await
+    }
+
+    async fn batch_stake(
+        &mut self,
+        user: &Keypair,
+        assets: &[Pubkey],
+    ) -> Result<(), BanksClientError> {
+        let mut ix = instruction(
+            nft_staking::accounts::BatchStake {
+                user: user.pubkey(),
+                collection: self.collection,
+                collection_info: self.collection_info,
+                stake_config: self.config,
+                user_account: self.user_pda(&user.pubkey()),
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            nft_staking::instruction::BatchStake {
+                lock_tier: LockTier::Flexible,
+            },
+        );
+        ix.accounts.extend(self.batch(assets));
+        self.send(&[ix], &[user]).await
+    }
+
+    async fn batch_unstake(
+        &mut self,
+        user: &Keypair,
+        assets: &[Pubkey],
+    ) -> Result<(), BanksClientError> {
+        let mut ix = instruction(
+            nft_staking::accounts::BatchUnstake {
+                user: user.pubkey(),
+                collection: self.collection,
+                stake_config: self.config,
+                user_account: self.user_pda(&user.pubkey()),
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            nft_staking::instruction::BatchUnstake {},
+        );
+        ix.accounts.extend(self.batch(assets));
+        self.send(&[ix], &[user]).await
+    }
+
+    /// `(asset, stake_account)` pairs for the batch instructions.
+    fn batch(&self, assets: &[Pubkey]) -> Vec<AccountMeta> {
+        assets
+            .iter()
+            .flat_map(|asset| {
+                [
+                    AccountMeta::new(*asset, false),
+                    AccountMeta::new(self.stake_pda(asset), false),
+                ]
+            })
+            .collect()
+    }
+
+    async fn emergency_unstake(
+        &mut self,
+        user: &Keypair,
+        asset: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            nft_staking::accounts::EmergencyUnstake {
+                user: user.pubkey(),
+                asset,
+                collection: self.collection,
+                stake_account: self.stake_pda(&asset),
+                stake_config: self.config,
+                user_account: self.user_pda(&user.pubkey()),
+                core_program: mpl_core::ID,
+                system_program: system_program::ID,
+            },
+            nft_staking::instruction::EmergencyUnstake {},
+        );
+        self.send(&[ix], &[user]).await
+    }
+
+    /// Updates the pool as `admin`, the config's admin by default.
+    async fn update_config(
+        &mut self,
+        admin: Option<&Keypair>,
+        reward_per_second: u64,
+        max_stake: u8,
+        freeze_period: u32,
+    ) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            nft_staking::accounts::UpdateConfig {
+                admin: admin.map_or(self.ctx.payer.pubkey(), |a| a.pubkey()),
+                stake_config: self.config,
+            },
+            nft_staking::instruction::UpdateConfig {
+                reward_per_second,
+                max_stake,
+                freeze_period,
+            },
+        );
+        self.send(&[ix], admin.as_slice()).await
+    }
+
+    /// Replaces the attribute multipliers as `admin`, the config's admin by
+    /// default.
+    async fn update_multipliers(
+        &mut self,
+        admin: Option<&Keypair>,
+        multipliers: Vec<AttributeMultiplier>,
+    ) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            nft_staking::accounts::UpdateConfig {
+                admin: admin.map_or(self.ctx.payer.pubkey(), |a| a.pubkey()),
+                stake_config: self.config,
+            },
+            nft_staking::instruction::UpdateMultipliers { multipliers },
+        );
+        self.send(&[ix], admin.as_slice()).await
+    }
+
+    /// Pauses the pool as `admin`, the config's admin by default.
+    async fn pause(&mut self, admin: Option<&Keypair>) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            nft_staking::accounts::UpdateConfig {
+                admin: admin.map_or(self.ctx.payer.pubkey(), |a| a.pubkey()),
+                stake_config: self.config,
+            },
+            nft_staking::instruction::Pause {},
+        );
+        self.send(&[ix], admin.as_slice()).await
+    }
+
+    /// Resumes the pool as `admin`, the config's admin by default.
+    async fn unpause(&mut self, admin: Option<&Keypair>) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            nft_staking::accounts::UpdateConfig {
+                admin: admin.map_or(self.ctx.payer.pubkey(), |a| a.pubkey()),
+                stake_config: self.config,
+            },
+            nft_staking::instruction::Unpause {},
+        );
+        self.send(&[ix], admin.as_slice()).await
+    }
+
+    async fn claim(&mut self, user: &Keypair) -> Result<(), BanksClientError> {
+        let ix = instruction(
+            nft_staking::accounts::Claim {
+                user: user.pubkey(),
+                rewards_ata: get_associated_token_address(&user.pubkey(), &self.reward_mint),
+                user_account: self.user_pda(&user.pubkey()),
+                reward_mint: self.reward_mint,
+                config: self.config,
+                token_program: spl_token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            nft_staking::instruction::Claim {},
+        );
+        self.send(&[ix], &[user]).await
+    }
+
+    async fn warp(&mut self, seconds: i64) {
+        let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    fn user_pda(&self, user: &Pubkey) -> Pubkey {
+        pda(&[b"user", self.config.as_ref(), user.as_ref()])
+    }
+
+    fn stake_pda(&self, asset: &Pubkey) -> Pubkey {
+        pda(&[b"stake", self.config.as_ref(), asset.as_ref()])
+    }
+
+    async fn user_account(&mut self, user: &Pubkey) -> UserAccount {
+        self.account(self.user_pda(user)).await
+    }
+
+    async fn rewards(&mut self, user: &Pubkey) -> u64 {
+        let address = get_associated_token_address(user, &self.reward_mint);
+        match self.ctx.banks_client.get_account(address).await.unwrap() {
+            Some(account) => TokenAccount::unpack(&account.data).unwrap().amount,
+            None => 0,
+        }
+    }
+
+    async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+}
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: nft_staking::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Default schedule: a budget far beyond any test and no halving.
+fn schedule() -> EmissionSchedule {
+    EmissionSchedule {
+        reward_per_second: REWARD_PER_SECOND,
+        reward_budget: 1_000 * DAY_REWARD,
+        halving_interval: 0,
+    }
+}
+
+/// Transactions land a few seconds apart, so emissions are only compared to
+/// within a minute's worth of rewards.
+fn assert_about(actual: u64, expected: u64) {
+    assert!(
+        actual.abs_diff(expected) <= 60 * REWARD_PER_SECOND,
+        "{actual} is not about {expected}"
+    );
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &nft_staking::ID).0
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn stake_warp_unstake_claim() {
+    let mut staking = Staking::new().await;
+    let config: StakeConfig = staking.account(staking.config).await;
+    assert_eq!(config.emission.reward_per_second, REWARD_PER_SECOND);
+
+    let user = staking.user().await;
+    let asset = staking.mint(&user).await;
+    staking.stake(&user, asset).await.unwrap();
+    assert_eq!(staking.user_account(&user.pubkey()).await.amount_staked, 1);
+
+    // still frozen
+    assert!(staking.unstake(&user, asset).await.is_err());
+
+    staking.warp(2 * SECONDS_PER_DAY).await;
+    staking.unstake(&user, asset).await.unwrap();
+    let account = staking.user_account(&user.pubkey()).await;
+    assert_eq!(account.amount_staked, 0);
+    assert_about(account.points, 2 * DAY_REWARD);
+
+    staking.claim(&user).await.unwrap();
+    assert_eq!(staking.user_account(&user.pubkey()).await.points, 0);
+    assert_eq!(staking.rewards(&user.pubkey()).await, account.points);
+
+    // nothing left to claim
+    assert!(staking.claim(&user).await.is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn claim_while_staked() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let asset = staking.mint(&user).await;
+    staking.stake(&user, asset).await.unwrap();
+
+    staking.warp(SECONDS_PER_DAY).await;
+    staking.claim(&user).await.unwrap();
+    assert_about(staking.rewards(&user.pubkey()).await, DAY_REWARD);
+    assert_eq!(staking.user_account(&user.pubkey()).await.amount_staked, 1);
+
+    staking.warp(SECONDS_PER_DAY).await;
+    staking.unstake(&user, asset).await.unwrap();
+    staking.claim(&user).await.unwrap();
+    assert_about(staking.rewards(&user.pubkey()).await, 2 * DAY_REWARD);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn rejects_foreign_assets() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let (foreign, foreign_info) = staking.create_collection().await;
+    let asset = staking.mint_from(&user, foreign, foreign_info).await;
+
+    // the collection is not on the config's allowlist
+    assert!(staking
+        .stake_in(&user, asset, foreign, foreign_info, LockTier::Flexible)
+        .await
+        .is_err());
+
+    // nor can the asset pass as a member of an allowlisted collection
+    assert!(staking.stake(&user, asset).await.is_err());
+    assert_eq!(staking.user_account(&user.pubkey()).await.amount_staked, 0);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn only_owners_can_stake() {
+    let mut staking = Staking::new().await;
+    let owner = staking.user().await;
+    let other = staking.user().await;
+    let asset = staking.mint(&owner).await;
+
+    assert!(staking.stake(&other, asset).await.is_err());
+    assert!(staking.batch_stake(&other, &[asset]).await.is_err());
+    assert_eq!(staking.user_account(&other.pubkey()).await.amount_staked, 0);
+
+    staking.stake(&owner, asset).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn takes_over_existing_freeze_delegate() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let asset = staking.mint(&user).await;
+    staking.add_freeze_delegate(&user, asset, false).await;
+
+    staking.stake(&user, asset).await.unwrap();
+    let stake: StakeAccount = staking.account(staking.stake_pda(&asset)).await;
+    assert_eq!(stake.prior_freeze_authority, Some(FreezeAuthority::Owner));
+
+    staking.warp(2 * SECONDS_PER_DAY).await;
+    staking.unstake(&user, asset).await.unwrap();
+    assert_eq!(staking.user_account(&user.pubkey()).await.amount_staked, 0);
+
+    // the owner's delegate is handed back, thawed
+    let data = staking
+        .ctx
+        .banks_client
+        .get_account(asset)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let delegate = Asset::deserialize(&data)
+        .unwrap()
+        .plugin_list
+        .freeze_delegate
+        .unwrap();
+    assert_eq!(delegate.base.authority.authority_type, AuthorityType::Owner);
+    assert!(!delegate.freeze_delegate.frozen);
+
+    // an asset someone else already froze cannot be staked
+    let frozen = staking.mint(&user).await;
+    staking.add_freeze_delegate(&user, frozen, true).await;
+    assert!(staking.stake(&user, frozen).await.is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn pools_are_independent() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let first = staking.mint(&user).await;
+    staking.stake(&user, first).await.unwrap();
+    let (first_config, first_user) = (staking.config, staking.user_pda(&user.pubkey()));
+
+    staking.open_pool(2, schedule(), vec![]).await;
+    staking.join(&user).await;
+    let second = staking.mint(&user).await;
+
+    // assets of the first pool's collection cannot be staked in the second
+    assert!(staking.stake(&user, first).await.is_err());
+    staking.stake(&user, second).await.unwrap();
+    assert_ne!(staking.config, first_config);
+
+    staking.warp(2 * SECONDS_PER_DAY).await;
+    staking.unstake(&user, second).await.unwrap();
+    staking.claim(&user).await.unwrap();
+    assert_about(staking.rewards(&user.pubkey()).await, 2 * DAY_REWARD);
+
+    let account: UserAccount = staking.account(first_user).await;
+    assert_eq!(account.config, first_config);
+    assert_eq!(account.amount_staked, 1);
+    assert_eq!(account.points, 0);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn attribute_multiplier_boosts_rewards() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+
+    // `mint_nft` records the minter as an attribute, which stands in for a
+    // rarity trait here
+    staking
+        .open_pool(
+            2,
+            schedule(),
+            vec![AttributeMultiplier {
+                key: "Minter".to_string(),
+                value: user.pubkey().to_string(),
+                multiplier_bps: 25_000,
+            }],
+        )
+        .await;
+    staking.join(&user).await;
+    let rare = staking.mint(&user).await;
+    staking.stake(&user, rare).await.unwrap();
+
+    let stake: StakeAccount = staking.account(staking.stake_pda(&rare)).await;
+    assert_eq!(stake.multiplier_bps, 25_000);
+
+    let other = staking.user().await;
+    let common = staking.mint(&other).await;
+    staking.stake(&other, common).await.unwrap();
+    let stake: StakeAccount = staking.account(staking.stake_pda(&common)).await;
+    assert_eq!(stake.multiplier_bps, 10_000);
+
+    // emissions split 2.5 : 1 by staked weight
+    staking.warp(7 * SECONDS_PER_DAY).await;
+    staking.claim(&user).await.unwrap();
+    staking.claim(&other).await.unwrap();
+    assert_about(staking.rewards(&user.pubkey()).await, 5 * DAY_REWARD);
+    assert_about(staking.rewards(&other.pubkey()).await, 2 * DAY_REWARD);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn admin_updates_multipliers_for_new_stakes() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let before = staking.mint(&user).await;
+    staking.stake(&user, before).await.unwrap();
+
+    let multipliers = vec![AttributeMultiplier {
+        key: "Minter".to_string(),
+        value: user.pubkey().to_string(),
+        multiplier_bps: 25_000,
+    }];
+    assert!(staking
+        .update_multipliers(Some(&user), multipliers.clone())
+        .await
+        .is_err());
+
+    staking.warp(SECONDS_PER_DAY).await;
+    staking.update_multipliers(None, multipliers).await.unwrap();
+    let config: StakeConfig = staking.account(staking.config).await;
+    assert_eq!(config.multipliers.len(), 1);
+    assert_about(config.rewards_emitted, DAY_REWARD);
+
+    // stakes keep the multiplier they were staked with
+    let after = staking.mint(&user).await;
+    staking.stake(&user, after).await.unwrap();
+    let stake: StakeAccount = staking.account(staking.stake_pda(&before)).await;
+    assert_eq!(stake.multiplier_bps, 10_000);
+    let stake: StakeAccount = staking.account(staking.stake_pda(&after)).await;
+    assert_eq!(stake.multiplier_bps, 25_000);
+    assert_eq!(
+        staking.user_account(&user.pubkey()).await.staked_weight,
+        35_000
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn lock_tier_boosts_rewards_until_expiry() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let locked = staking.mint(&user).await;
+    staking
+        .stake_locked(&user, locked, LockTier::Quarter)
+        .await
+        .unwrap();
+
+    let stake: StakeAccount = staking.account(staking.stake_pda(&locked)).await;
+    assert_eq!(stake.lock_tier, LockTier::Quarter);
+    assert_eq!(stake.weight(), 15_000);
+    assert_eq!(
+        stake.unlock_at,
+        stake.staked_at + (FREEZE_PERIOD + 90) as i64 * SECONDS_PER_DAY
+    );
+
+    let other = staking.user().await;
+    let flexible = staking.mint(&other).await;
+    staking.stake(&other, flexible).await.unwrap();
+
+    // emissions split 1.5 : 1 by staked weight
+    staking.warp(5 * SECONDS_PER_DAY).await;
+    staking.unstake(&other, flexible).await.unwrap();
+    assert!(staking.unstake(&user, locked).await.is_err());
+    staking.claim(&user).await.unwrap();
+    staking.claim(&other).await.unwrap();
+    assert_about(staking.rewards(&user.pubkey()).await, 3 * DAY_REWARD);
+    assert_about(staking.rewards(&other.pubkey()).await, 2 * DAY_REWARD);
+
+    // the freeze period comes on top of the 90 day lock
+    staking.warp(85 * SECONDS_PER_DAY).await;
+    assert!(staking.unstake(&user, locked).await.is_err());
+    staking.warp(FREEZE_PERIOD as i64 * SECONDS_PER_DAY).await;
+    staking.unstake(&user, locked).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn batch_stake_and_unstake() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let mut assets = vec![];
+    for _ in 0..3 {
+        assets.push(staking.mint(&user).await);
+    }
+
+    // a duplicate pair fails the whole batch
+    assert!(staking
+        .batch_stake(&user, &[assets[0], assets[0]])
+        .await
+        .is_err());
+
+    // a stake account address that already holds lamports is still created
+    let prefund = system_instruction::transfer(
+        &staking.ctx.payer.pubkey(),
+        &staking.stake_pda(&assets[1]),
+        1_000,
+    );
+    staking.send(&[prefund], &[]).await.unwrap();
+
+    staking.batch_stake(&user, &assets).await.unwrap();
+    let stake: StakeAccount = staking.account(staking.stake_pda(&assets[1])).await;
+    assert_eq!(stake.owner, user.pubkey());
+    let account = staking.user_account(&user.pubkey()).await;
+    assert_eq!(account.amount_staked, 3);
+    assert_eq!(account.staked_weight, 30_000);
+    let config: StakeConfig = staking.account(staking.config).await;
+    assert_eq!(config.total_weight, 30_000);
+
+    // exceeding max_stake rejects the batch atomically
+    let mut extra = vec![];
+    for _ in 0..MAX_STAKE - 2 {
+        extra.push(staking.mint(&user).await);
+    }
+    assert!(staking.batch_stake(&user, &extra).await.is_err());
+    assert_eq!(staking.user_account(&user.pubkey()).await.amount_staked, 3);
+
+    staking.warp(2 * SECONDS_PER_DAY).await;
+    staking.batch_unstake(&user, &assets[..2]).await.unwrap();
+    let account = staking.user_account(&user.pubkey()).await;
+    assert_eq!(account.amount_staked, 1);
+    assert_eq!(account.staked_weight, 10_000);
+    assert_about(account.points, 2 * DAY_REWARD);
+
+    staking.unstake(&user, assets[2]).await.unwrap();
+    assert_eq!(staking.user_account(&user.pubkey()).await.amount_staked, 0);
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn admin_updates_and_emergency_unstake() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let asset = staking.mint(&user).await;
+    staking.stake(&user, asset).await.unwrap();
+    let kept = staking.mint(&user).await;
+    staking.stake(&user, kept).await.unwrap();
+
+    assert!(staking
+        .update_config(Some(&user), 2 * REWARD_PER_SECOND, MAX_STAKE, 0)
+        .await
+        .is_err());
+    assert!(staking.pause(Some(&user)).await.is_err());
+    assert!(staking.unpause(None).await.is_err());
+
+    staking
+        .update_config(None, 2 * REWARD_PER_SECOND, MAX_STAKE, 0)
+        .await
+        .unwrap();
+    let config: StakeConfig = staking.account(staking.config).await;
+    assert_eq!(config.emission.reward_per_second, 2 * REWARD_PER_SECOND);
+    assert_eq!(config.freeze_period, 0);
+
+    staking.warp(SECONDS_PER_DAY).await;
+    staking.pause(None).await.unwrap();
+    assert!(staking.pause(None).await.is_err());
+    assert!(staking.claim(&user).await.is_err());
+    let other = staking.mint(&user).await;
+    assert!(staking.stake(&user, other).await.is_err());
+
+    // only the unstaked asset's unsettled rewards are forfeited
+    staking.emergency_unstake(&user, asset).await.unwrap();
+    let account = staking.user_account(&user.pubkey()).await;
+    assert_eq!(account.amount_staked, 1);
+    assert_eq!(account.staked_weight, 10_000);
+    assert_about(account.points, 2 * DAY_REWARD / 2);
+
+    assert!(staking.unpause(Some(&user)).await.is_err());
+    staking.unpause(None).await.unwrap();
+    staking.stake(&user, asset).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn emergency_unstake_ignores_the_freeze_period() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let frozen = staking.mint(&user).await;
+    staking.stake(&user, frozen).await.unwrap();
+    let kept = staking.mint(&user).await;
+    staking.stake(&user, kept).await.unwrap();
+
+    // still inside the freeze period, and the pool is running
+    staking.warp(SECONDS_PER_DAY / 2).await;
+    assert!(staking.unstake(&user, frozen).await.is_err());
+    staking.emergency_unstake(&user, frozen).await.unwrap();
+    let account = staking.user_account(&user.pubkey()).await;
+    assert_eq!(account.amount_staked, 1);
+    assert_eq!(account.staked_weight, 10_000);
+    assert_about(account.points, DAY_REWARD / 4);
+
+    // emissions up to the exit were split before the weight left
+    staking.warp(SECONDS_PER_DAY).await;
+    staking.claim(&user).await.unwrap();
+    assert_about(
+        staking.rewards(&user.pubkey()).await,
+        DAY_REWARD + DAY_REWARD / 4,
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn raising_freeze_period_keeps_existing_unlocks() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let asset = staking.mint(&user).await;
+    staking.stake(&user, asset).await.unwrap();
+
+    staking
+        .update_config(None, REWARD_PER_SECOND, MAX_STAKE, 30)
+        .await
+        .unwrap();
+    let later = staking.mint(&user).await;
+    staking.stake(&user, later).await.unwrap();
+
+    staking.warp(FREEZE_PERIOD as i64 * SECONDS_PER_DAY).await;
+    staking.unstake(&user, asset).await.unwrap();
+    assert!(staking.unstake(&user, later).await.is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs target/deploy/nft_staking.so; run by anchor test and CI"]
+async fn emissions_halve_and_stop_at_budget() {
+    let mut staking = Staking::new().await;
+    let user = staking.user().await;
+    let budget = DAY_REWARD * 9 / 5;
+    staking
+        .open_pool(
+            2,
+            EmissionSchedule {
+                reward_per_second: REWARD_PER_SECOND,
+                reward_budget: budget,
+                halving_interval: SECONDS_PER_DAY,
+            },
+            vec![],
+        )
+        .await;
+    staking.join(&user).await;
+    let asset = staking.mint(&user).await;
+    staking.stake(&user, asset).await.unwrap();
+
+    staking.warp(SECONDS_PER_DAY).await;
+    staking.claim(&user).await.unwrap();
+    assert_about(staking.rewards(&user.pubkey()).await, DAY_REWARD);
+
+    staking.warp(SECONDS_PER_DAY).await;
+    staking.claim(&user).await.unwrap();
+    assert_about(staking.rewards(&user.pubkey()).await, DAY_REWARD * 3 / 2);
+
+    // 1 + 1/2 + 1/4 days of emissions would pass the budget
+    staking.warp(5 * SECONDS_PER_DAY).await;
+    staking.claim(&user).await.unwrap();
+    assert_eq!(staking.rewards(&user.pubkey()).await, budget);
+    let config: StakeConfig = staking.account(staking.config).await;
+    assert_eq!(config.rewards_emitted, budget);
+
+    staking.warp(SECONDS_PER_DAY).await;
+    assert!(staking.claim(&user).await.is_err());
+}
@@ -20,9 +20,18 @@ describe("anchor-nft-staking-q4-25", () => {
   const asset = Keypair.generate();
 
   // Config parameters
-  const pointsPerStake = 10;
+  const seed = new anchor.BN(1);
+  const emission = {
+    rewardPerSecond: new anchor.BN(1_000),
+    rewardBudget: new anchor.BN(1_000_000_000_000),
+    halvingInterval: new anchor.BN(0), // no halving
+  };
   const maxStake = 5;
   const freezePeriod = 0; // 0 days for testing
+  // mint_nft records the minter as an attribute; boost this user's NFTs 2x
+  const multipliers = [
+    { key: "Minter", value: user.publicKey.toString(), multiplierBps: 20_000 },
+  ];
 
   // PDAs
   let configPda: PublicKey;
@@ -44,19 +53,19 @@ describe("anchor-nft-staking-q4-25", () => {
 
     // Derive PDAs
     configPda = PublicKey.findProgramAddressSync(
-      [Buffer.from("config")],
+      [Buffer.from("config"), seed.toArrayLike(Buffer, "le", 8)],
       program.programId
     )[0];
     console.log(`Config PDA: ${configPda.toString()}`);
 
     rewardMintPda = PublicKey.findProgramAddressSync(
-      [Buffer.from("reward"), configPda.toBuffer()],
+      [Buffer.from("rewards"), configPda.toBuffer()],
       program.programId
     )[0];
     console.log(`Reward Mint PDA: ${rewardMintPda.toString()}`);
 
     userAccountPda = PublicKey.findProgramAddressSync(
-      [Buffer.from("user"), user.publicKey.toBuffer()],
+      [Buffer.from("user"), configPda.toBuffer(), user.publicKey.toBuffer()],
       program.programId
     )[0];
     console.log(`User Account PDA: ${userAccountPda.toString()}`);
@@ -80,10 +89,56 @@ describe("anchor-nft-staking-q4-25", () => {
     console.log(`Rewards ATA: ${rewardsAtaPda.toString()}`);
   });
 
+  describe("Create Collection", () => {
+    it("Create a Metaplex Core collection", async () => {
+      const args = {
+        name: "Test Staking Collection",
+        uri: "https://example.com/collection.json",
+        nftName: "Staked NFT",
+        nftUri: "https://example.com/nft.json",
+      };
+
+      const tx = await program.methods
+        .createCollection(args)
+        .accountsStrict({
+          authority: admin.publicKey,
+          collection: collection.publicKey,
+          collectionInfo: collectionInfoPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([collection])
+        .rpc();
+
+      console.log(`Create Collection tx: ${tx}`);
+
+      const collectionInfo = await program.account.collectionInfo.fetch(
+        collectionInfoPda
+      );
+      assert.equal(
+        collectionInfo.collection.toString(),
+        collection.publicKey.toString()
+      );
+      assert.equal(
+        collectionInfo.authority.toString(),
+        admin.publicKey.toString()
+      );
+      assert.equal(collectionInfo.name, args.name);
+      assert.equal(collectionInfo.nftName, args.nftName);
+      console.log("Collection created successfully");
+    });
+  });
+
   describe("Initialize Config", () => {
     it("Initialize the staking config", async () => {
       const tx = await program.methods
-        .initializeConfig(pointsPerStake, maxStake, freezePeriod)
+        .initializeConfig(
+          seed,
+          maxStake,
+          freezePeriod,
+          emission,
+          multipliers
+        )
         .accountsStrict({
           admin: admin.publicKey,
           stakeConfig: configPda,
@@ -91,14 +146,26 @@ describe("anchor-nft-staking-q4-25", () => {
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
+        .remainingAccounts([
+          { pubkey: collectionInfoPda, isWritable: false, isSigner: false },
+        ])
         .rpc();
 
       console.log(`Initialize Config tx: ${tx}`);
 
       const config = await program.account.stakeConfig.fetch(configPda);
-      assert.equal(config.pointsPerStake, pointsPerStake);
+      assert.ok(config.seed.eq(seed));
+      assert.equal(config.admin.toString(), admin.publicKey.toString());
+      assert.equal(config.paused, false);
+      assert.ok(config.emission.rewardPerSecond.eq(emission.rewardPerSecond));
+      assert.ok(config.emission.rewardBudget.eq(emission.rewardBudget));
+      assert.ok(config.rewardsEmitted.isZero());
       assert.equal(config.maxStake, maxStake);
       assert.equal(config.freezePeriod, freezePeriod);
+      assert.deepEqual(
+        config.collections.map((c) => c.toString()),
+        [collectionInfoPda.toString()]
+      );
       console.log("Config initialized successfully");
     });
   });
@@ -109,6 +176,7 @@ describe("anchor-nft-staking-q4-25", () => {
         .initializeUser()
         .accountsStrict({
           user: user.publicKey,
+          stakeConfig: configPda,
           userAccount: userAccountPda,
           systemProgram: SystemProgram.programId,
         })
@@ -120,52 +188,13 @@ describe("anchor-nft-staking-q4-25", () => {
       const userAccount = await program.account.userAccount.fetch(
         userAccountPda
       );
-      assert.equal(userAccount.points, 0);
+      assert.equal(userAccount.config.toString(), configPda.toString());
+      assert.ok(userAccount.points.isZero());
       assert.equal(userAccount.amountStaked, 0);
       console.log("User account initialized successfully");
     });
   });
 
-  describe("Create Collection", () => {
-    it("Create a Metaplex Core collection", async () => {
-      const args = {
-        name: "Test Staking Collection",
-        uri: "https://example.com/collection.json",
-        nftName: "Staked NFT",
-        nftUri: "https://example.com/nft.json",
-      };
-
-      const tx = await program.methods
-        .createCollection(args)
-        .accountsStrict({
-          authority: admin.publicKey,
-          collection: collection.publicKey,
-          collectionInfo: collectionInfoPda,
-          coreProgram: MPL_CORE_PROGRAM_ID,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([collection])
-        .rpc();
-
-      console.log(`Create Collection tx: ${tx}`);
-
-      const collectionInfo = await program.account.collectionInfo.fetch(
-        collectionInfoPda
-      );
-      assert.equal(
-        collectionInfo.collection.toString(),
-        collection.publicKey.toString()
-      );
-      assert.equal(
-        collectionInfo.authority.toString(),
-        admin.publicKey.toString()
-      );
-      assert.equal(collectionInfo.name, args.name);
-      assert.equal(collectionInfo.nftName, args.nftName);
-      console.log("Collection created successfully");
-    });
-  });
-
   describe("Mint NFT", () => {
     it("Mint an NFT without FreezeDelegate", async () => {
       const tx = await program.methods
@@ -198,11 +227,12 @@ describe("anchor-nft-staking-q4-25", () => {
       assert.equal(userAccountBefore.amountStaked, 0);
 
       const tx = await program.methods
-        .stake()
+        .stake({ flexible: {} })
         .accountsStrict({
           user: user.publicKey,
           asset: asset.publicKey,
           collection: collection.publicKey,
+          collectionInfo: collectionInfoPda,
           stakeAccount: stakeAccountPda,
           stakeConfig: configPda,
           userAccount: userAccountPda,
@@ -220,15 +250,56 @@ describe("anchor-nft-staking-q4-25", () => {
       assert.equal(stakeAccount.owner.toString(), user.publicKey.toString());
       assert.equal(stakeAccount.mint.toString(), asset.publicKey.toString());
       assert.ok(stakeAccount.stakedAt > new anchor.BN(0));
+      assert.ok(stakeAccount.unlockAt.eq(stakeAccount.stakedAt));
+      assert.equal(stakeAccount.multiplierBps, 20_000);
+      assert.deepEqual(stakeAccount.lockTier, { flexible: {} });
+      assert.isNull(stakeAccount.priorFreezeAuthority);
 
       const userAccountAfter = await program.account.userAccount.fetch(
         userAccountPda
       );
       assert.equal(userAccountAfter.amountStaked, 1);
+      assert.ok(userAccountAfter.stakedWeight.eqn(20_000));
       console.log("NFT staked successfully");
     });
   });
 
+  describe("Claim While Staked", () => {
+    it("Settles accrued rewards without unfreezing the NFT", async () => {
+      // let a few seconds of emissions accrue
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      await program.methods
+        .claim()
+        .accountsStrict({
+          user: user.publicKey,
+          rewardsAta: rewardsAtaPda,
+          config: configPda,
+          userAccount: userAccountPda,
+          rewardMint: rewardMintPda,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const balance = await connection.getTokenAccountBalance(rewardsAtaPda);
+      assert.ok(Number(balance.value.amount) > 0, "Rewards should be minted");
+
+      const stakeAccount = await program.account.stakeAccount.fetch(
+        stakeAccountPda
+      );
+      assert.equal(stakeAccount.owner.toString(), user.publicKey.toString());
+
+      const userAccountAfter = await program.account.userAccount.fetch(
+        userAccountPda
+      );
+      assert.equal(userAccountAfter.amountStaked, 1);
+      console.log("Claim left the NFT staked");
+    });
+  });
+
   describe("Unstake NFT", () => {
     it("Unstake the NFT and remove FreezeDelegate plugin", async () => {
       // Wait for freeze period if needed (0 in this test)
@@ -272,12 +343,12 @@ describe("anchor-nft-staking-q4-25", () => {
         userAccountPda
       );
       assert.equal(userAccountAfter.amountStaked, 0);
-      // Points should be awarded (time_elapsed * points_per_stake)
-      assert.ok(userAccountAfter.points >= pointsBefore);
+      // Rewards accrued since the last settlement are banked on unstake
+      assert.ok(userAccountAfter.points.gte(pointsBefore));
       console.log(
-        `NFT unstaked successfully, points earned: ${
-          userAccountAfter.points - pointsBefore
-        }`
+        `NFT unstaked successfully, rewards earned: ${userAccountAfter.points.sub(
+          pointsBefore
+        )}`
       );
     });
   });
@@ -289,7 +360,7 @@ describe("anchor-nft-staking-q4-25", () => {
       );
       const pointsBefore = userAccountBefore.points;
 
-      if (pointsBefore === 0) {
+      if (pointsBefore.isZero()) {
         console.log("No points to claim, skipping claim test");
         return;
       }
@@ -314,7 +385,7 @@ describe("anchor-nft-staking-q4-25", () => {
       const userAccountAfter = await program.account.userAccount.fetch(
         userAccountPda
       );
-      assert.equal(userAccountAfter.points, 0, "Points should be reset to 0");
+      assert.ok(userAccountAfter.points.isZero(), "Points should be reset to 0");
 
       // Check token balance
       const rewardsAta = await connection.getAccountInfo(rewardsAtaPda);
@@ -322,4 +393,191 @@ describe("anchor-nft-staking-q4-25", () => {
       console.log("Rewards claimed successfully");
     });
   });
+
+  describe("Batch Stake and Unstake", () => {
+    const batch = [Keypair.generate(), Keypair.generate()];
+    const pairs = () =>
+      batch.flatMap((a) => [
+        { pubkey: a.publicKey, isWritable: true, isSigner: false },
+        {
+          pubkey: PublicKey.findProgramAddressSync(
+            [Buffer.from("stake"), configPda.toBuffer(), a.publicKey.toBuffer()],
+            program.programId
+          )[0],
+          isWritable: true,
+          isSigner: false,
+        },
+      ]);
+
+    before(async () => {
+      for (const a of batch) {
+        await program.methods
+          .mintNft()
+          .accountsStrict({
+            minter: user.publicKey,
+            asset: a.publicKey,
+            collection: collection.publicKey,
+            collectionInfo: collectionInfoPda,
+            coreProgram: MPL_CORE_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user, a])
+          .rpc();
+      }
+    });
+
+    it("Stakes several NFTs in one transaction", async () => {
+      const tx = await program.methods
+        .batchStake({ flexible: {} })
+        .accountsStrict({
+          user: user.publicKey,
+          collection: collection.publicKey,
+          collectionInfo: collectionInfoPda,
+          stakeConfig: configPda,
+          userAccount: userAccountPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(pairs())
+        .signers([user])
+        .rpc();
+
+      console.log(`Batch Stake tx: ${tx}`);
+
+      const userAccount = await program.account.userAccount.fetch(
+        userAccountPda
+      );
+      assert.equal(userAccount.amountStaked, batch.length);
+      console.log("NFTs batch staked successfully");
+    });
+
+    it("Unstakes them in one transaction", async () => {
+      const tx = await program.methods
+        .batchUnstake()
+        .accountsStrict({
+          user: user.publicKey,
+          collection: collection.publicKey,
+          stakeConfig: configPda,
+          userAccount: userAccountPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(pairs())
+        .signers([user])
+        .rpc();
+
+      console.log(`Batch Unstake tx: ${tx}`);
+
+      const userAccount = await program.account.userAccount.fetch(
+        userAccountPda
+      );
+      assert.equal(userAccount.amountStaked, 0);
+      console.log("NFTs batch unstaked successfully");
+    });
+  });
+
+  describe("Admin", () => {
+    const emergencyAsset = Keypair.generate();
+    const emergencyStakePda = () =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("stake"),
+          configPda.toBuffer(),
+          emergencyAsset.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+    it("Rejects config updates from non-admins", async () => {
+      try {
+        await program.methods
+          .updateConfig(
+            emission.rewardPerSecond.muln(2),
+            maxStake,
+            freezePeriod
+          )
+          .accountsStrict({ admin: user.publicKey, stakeConfig: configPda })
+          .signers([user])
+          .rpc();
+        assert.fail("Non-admin update should fail");
+      } catch (err) {
+        assert.include(err.toString(), "NotAdmin");
+      }
+    });
+
+    it("Updates the config", async () => {
+      await program.methods
+        .updateConfig(emission.rewardPerSecond.muln(2), maxStake, freezePeriod)
+        .accountsStrict({ admin: admin.publicKey, stakeConfig: configPda })
+        .rpc();
+
+      const config = await program.account.stakeConfig.fetch(configPda);
+      assert.ok(
+        config.emission.rewardPerSecond.eq(emission.rewardPerSecond.muln(2))
+      );
+    });
+
+    it("Lets users emergency unstake while paused", async () => {
+      await program.methods
+        .mintNft()
+        .accountsStrict({
+          minter: user.publicKey,
+          asset: emergencyAsset.publicKey,
+          collection: collection.publicKey,
+          collectionInfo: collectionInfoPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user, emergencyAsset])
+        .rpc();
+      await program.methods
+        .stake({ flexible: {} })
+        .accountsStrict({
+          user: user.publicKey,
+          asset: emergencyAsset.publicKey,
+          collection: collection.publicKey,
+          collectionInfo: collectionInfoPda,
+          stakeAccount: emergencyStakePda(),
+          stakeConfig: configPda,
+          userAccount: userAccountPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      await program.methods
+        .pause()
+        .accountsStrict({ admin: admin.publicKey, stakeConfig: configPda })
+        .rpc();
+
+      const tx = await program.methods
+        .emergencyUnstake()
+        .accountsStrict({
+          user: user.publicKey,
+          asset: emergencyAsset.publicKey,
+          collection: collection.publicKey,
+          stakeAccount: emergencyStakePda(),
+          stakeConfig: configPda,
+          userAccount: userAccountPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      console.log(`Emergency Unstake tx: ${tx}`);
+
+      const userAccount = await program.account.userAccount.fetch(
+        userAccountPda
+      );
+      assert.equal(userAccount.amountStaked, 0);
+
+      await program.methods
+        .unpause()
+        .accountsStrict({ admin: admin.publicKey, stakeConfig: configPda })
+        .rpc();
+      console.log("NFT returned while paused");
+    });
+  });
 });